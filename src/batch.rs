@@ -0,0 +1,273 @@
+use crate::cell::{Cell, CellConfig, ReplicationSummary};
+use crate::error::ReplicationError;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+/// Run one fresh cell per configuration, each from its own seed
+pub fn batch_replicate(
+    configs: &[CellConfig],
+) -> Result<Vec<ReplicationSummary>, ReplicationError> {
+    configs
+        .iter()
+        .map(|config| Cell::from_config(config)?.full_replication(config.g_phase_prob, config.seed))
+        .collect()
+}
+
+/// Run one fresh cell per seed and return the number of S-phase iterations each took
+pub fn batch_s_phase_durations(
+    config: &CellConfig,
+    seeds: &[u64],
+) -> Result<Vec<usize>, ReplicationError> {
+    seeds
+        .iter()
+        .map(|&seed| {
+            let summary = Cell::from_config(config)?.full_replication(config.g_phase_prob, seed)?;
+            Ok(summary.num_iterations)
+        })
+        .collect()
+}
+
+/// Run one fresh cell per seed with timing recorded and return each cell's
+/// [`TimingTrack::binned_replication_time`](crate::TimingTrack::binned_replication_time),
+/// one row per seed in seed order
+pub fn timing_matrix(
+    config: &CellConfig,
+    seeds: &[u64],
+    n_bins: usize,
+) -> Result<Vec<Vec<f64>>, ReplicationError> {
+    if n_bins == 0 {
+        return Err(ReplicationError::InvalidConfig(
+            "n_bins must be positive".to_string(),
+        ));
+    }
+    seeds
+        .iter()
+        .map(|&seed| {
+            let mut cell = Cell::from_config(config)?.with_timing_track();
+            cell.full_replication(config.g_phase_prob, seed)?;
+            cell.timing_track()
+                .expect("timing track was enabled")
+                .binned_replication_time(n_bins)
+        })
+        .collect()
+}
+
+/// Sample variance across cells of the mean replication time in each of `n_bins` equal
+/// bins, from the [`timing_matrix`] of one cell per seed. Low values mark a reproducible
+/// timing program, high values stochastic zones. NaN with fewer than two seeds, and
+/// zero bins are rejected as in [`timing_matrix`].
+pub fn timing_variance(
+    config: &CellConfig,
+    seeds: &[u64],
+    n_bins: usize,
+) -> Result<Vec<f64>, ReplicationError> {
+    let matrix = timing_matrix(config, seeds, n_bins)?;
+    let num_cells = matrix.len() as f64;
+    Ok((0..n_bins)
+        .map(|bin| {
+            let centre = matrix.iter().map(|row| row[bin]).sum::<f64>() / num_cells;
+            matrix
+                .iter()
+                .map(|row| (row[bin] - centre).powi(2))
+                .sum::<f64>()
+                / (num_cells - 1.0)
+        })
+        .collect())
+}
+
+/// Fraction of all fired origins falling in each of `n_bins` equal bins, pooled over one
+/// run of a copy of `template` per seed. Unlike the other batch runners this takes a
+/// built cell, so density profiles and other topology shape the landscape.
+pub fn origin_landscape(
+    template: &Cell,
+    g_phase_prob: f64,
+    seeds: &[u64],
+    n_bins: usize,
+) -> Result<Vec<f64>, ReplicationError> {
+    if n_bins == 0 {
+        return Err(ReplicationError::InvalidConfig(
+            "n_bins must be positive".to_string(),
+        ));
+    }
+    let genome_length = template.genome().genome_length();
+    let mut counts = vec![0usize; n_bins];
+    for &seed in seeds {
+        let mut cell = template.clone();
+        cell.full_replication(g_phase_prob, seed)?;
+        for (_, position) in cell.firing_log() {
+            counts[position * n_bins / genome_length] += 1;
+        }
+    }
+    let total: usize = counts.iter().sum();
+    Ok(counts
+        .iter()
+        .map(|&count| count as f64 / total as f64)
+        .collect())
+}
+
+/// Replicated fraction of one fresh cell per seed after `iteration` S-phase iterations,
+/// in seed order. Cells entering S-phase immediately, as in [`fork_count_timeseries`],
+/// and cells finishing sooner give 1.0.
+pub fn fraction_at_iteration(
+    config: &CellConfig,
+    seeds: &[u64],
+    iteration: usize,
+) -> Result<Vec<f64>, ReplicationError> {
+    seeds
+        .iter()
+        .map(|&seed| {
+            let mut cell = Cell::from_config(config)?;
+            let mut rng = ChaCha8Rng::seed_from_u64(seed);
+            cell.advance_by(iteration, &mut rng)?;
+            Ok(cell.genome().fraction_replicated())
+        })
+        .collect()
+}
+
+/// Active fork count of one S-phase run from `seed`, starting from the unreplicated
+/// genome and then after each iteration
+pub fn fork_count_timeseries(
+    config: &CellConfig,
+    seed: u64,
+) -> Result<Vec<usize>, ReplicationError> {
+    let mut cell = Cell::from_config(config)?;
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    let mut counts = vec![cell.genome().active_fork_count()];
+    while !cell.is_fully_replicated() {
+        cell.advance_by(1, &mut rng)?;
+        counts.push(cell.genome().active_fork_count());
+    }
+    Ok(counts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stats::mean;
+
+    #[test]
+    fn more_replicators_shorten_s_phase() {
+        let seeds: Vec<u64> = (0..10).collect();
+        let few = CellConfig {
+            genome_length: 100_000,
+            num_replicators: 2,
+            replication_rate: 50,
+            firing_probability: 0.1,
+            g_phase_prob: 0.9,
+            seed: 0,
+        };
+        let many = CellConfig {
+            num_replicators: 20,
+            ..few.clone()
+        };
+        let few_durations = batch_s_phase_durations(&few, &seeds).unwrap();
+        let many_durations = batch_s_phase_durations(&many, &seeds).unwrap();
+        assert_eq!(few_durations.len(), seeds.len());
+        assert!(mean(&many_durations) < mean(&few_durations));
+    }
+
+    #[test]
+    fn batch_replicate_runs_each_config_with_its_seed() {
+        let configs: Vec<CellConfig> = (0..3)
+            .map(|seed| CellConfig::new(20_000, 4).with_seed(seed))
+            .collect();
+        let summaries = batch_replicate(&configs).unwrap();
+        let durations = batch_s_phase_durations(&configs[0], &[0, 1, 2]).unwrap();
+        assert_eq!(
+            summaries
+                .iter()
+                .map(|summary| summary.num_iterations)
+                .collect::<Vec<_>>(),
+            durations
+        );
+    }
+
+    #[test]
+    fn favoured_zone_is_a_landscape_peak() {
+        let template = Cell::new(50_000, 4, 50).with_density_profile(vec![(20_000..25_000, 100.0)]);
+        let seeds: Vec<u64> = (0..10).collect();
+        let landscape = origin_landscape(&template, 0.5, &seeds, 10).unwrap();
+        assert!((landscape.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+        assert!(landscape
+            .iter()
+            .enumerate()
+            .all(|(bin, frequency)| bin == 4 || 2.0 * frequency < landscape[4]));
+    }
+
+    #[test]
+    fn landscape_without_bins_is_rejected() {
+        let template = Cell::new(50_000, 4, 50);
+        assert!(matches!(
+            origin_landscape(&template, 0.5, &[0], 0),
+            Err(ReplicationError::InvalidConfig(_))
+        ));
+    }
+
+    #[test]
+    fn fork_count_rises_then_falls_to_zero() {
+        let config = CellConfig {
+            genome_length: 50_000,
+            num_replicators: 8,
+            ..CellConfig::default()
+        };
+        let counts = fork_count_timeseries(&config, 5).unwrap();
+        assert_eq!(counts.first(), Some(&0));
+        assert_eq!(counts.last(), Some(&0));
+        assert!(counts.iter().max().unwrap() > &2);
+    }
+
+    #[test]
+    fn certain_firing_makes_timing_reproducible() {
+        let seeds: Vec<u64> = (0..20).collect();
+        let stochastic = CellConfig::new(50_000, 8)
+            .with_firing_probability(1e-4)
+            .with_g_phase_prob(0.0);
+        let certain = stochastic.clone().with_firing_probability(1.0);
+        let stochastic_variance = timing_variance(&stochastic, &seeds, 10).unwrap();
+        let certain_variance = timing_variance(&certain, &seeds, 10).unwrap();
+        assert_eq!(certain_variance.len(), 10);
+        assert!(certain_variance.iter().all(|variance| *variance >= 0.0));
+        let total = |variances: &[f64]| variances.iter().sum::<f64>();
+        assert!(total(&certain_variance) < total(&stochastic_variance));
+        assert!(matches!(
+            timing_variance(&certain, &seeds, 0),
+            Err(ReplicationError::InvalidConfig(_))
+        ));
+    }
+
+    #[test]
+    fn fraction_replicated_grows_with_iteration() {
+        let config = CellConfig::new(50_000, 8);
+        let seeds: Vec<u64> = (0..10).collect();
+        let fractions = |iteration| fraction_at_iteration(&config, &seeds, iteration).unwrap();
+        let mean_fraction = |fractions: Vec<f64>| fractions.iter().sum::<f64>() / 10.0;
+        let (early, late) = (fractions(20), fractions(60));
+        assert_eq!(early.len(), seeds.len());
+        assert!(mean_fraction(early) < mean_fraction(late));
+        assert!(fractions(usize::MAX)
+            .iter()
+            .all(|&fraction| fraction == 1.0));
+    }
+
+    #[test]
+    fn timing_matrix_has_one_row_per_seed() {
+        let config = CellConfig {
+            genome_length: 20_000,
+            num_replicators: 4,
+            ..CellConfig::default()
+        };
+        let matrix = timing_matrix(&config, &[3, 1, 2], 10).unwrap();
+        assert_eq!(matrix.len(), 3);
+        assert!(matrix.iter().all(|row| row.len() == 10));
+        assert!(matrix.iter().flatten().all(|time| !time.is_nan()));
+        assert_eq!(matrix[1], timing_matrix(&config, &[1], 10).unwrap()[0]);
+        assert!(matches!(
+            timing_matrix(&config, &[3, 1, 2], 0),
+            Err(ReplicationError::InvalidConfig(_))
+        ));
+        assert!(matches!(
+            timing_matrix(&config, &[], 0),
+            Err(ReplicationError::InvalidConfig(_))
+        ));
+    }
+}
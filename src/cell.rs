@@ -0,0 +1,2865 @@
+use crate::error::ReplicationError;
+use crate::genome::{DomainTiming, Genome, ReplicationDomain};
+use crate::sampler::{FiringSampler, LengthWeightedSampler};
+use crate::stats::{histogram, mean, variance};
+use crate::steps::{AdvanceStats, StepIter};
+use crate::timing::Clock;
+#[cfg(feature = "std")]
+use crate::timing::TimingTrack;
+use alloc::boxed::Box;
+use alloc::string::ToString;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::num::NonZeroUsize;
+use core::ops::Range;
+use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub enum CellState {
+    #[default]
+    GPhase,
+    SPhase,
+    /// Entered once the genome is fully replicated
+    G2Phase,
+}
+
+/// When origin positions are chosen
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum LicensingMode {
+    /// Pick a new origin whenever a replicator is free
+    #[default]
+    Continuous,
+    /// License origins before S-phase, `num_replicators` unless
+    /// [`Cell::with_licensed_origin_count`] says otherwise. Each licensed origin is then
+    /// activated with the firing probability per step, with no further licensing.
+    PreLicense,
+}
+
+#[derive(Debug, Clone)]
+pub struct Cell {
+    genome: Genome,
+    num_replicators: usize,
+    unassigned_replicators: usize,
+    cell_state: CellState,
+    replication_rate: usize,
+    firing_probability: f64,
+    checkpoint: Option<CheckpointConfig>,
+    fork_slowdown: Option<ForkSlowdown>,
+    fork_suppression: Option<ForkSuppression>,
+    max_iterations: usize,
+    iteration: usize,
+    firing_log: Vec<(usize, usize)>,
+    termination_sites: Vec<usize>,
+    lagging_strand_bases: usize,
+    okazaki_fragment_size: usize,
+    #[cfg(feature = "std")]
+    timing_track: Option<TimingTrack>,
+    firing_sampler: Box<dyn FiringSampler>,
+    refire_rejections: usize,
+    utilization: Vec<usize>,
+    licensing: LicensingMode,
+    /// Licensed origins yet to fire, chosen on the first assignment in pre-licensing mode
+    licensed_origins: Option<Vec<usize>>,
+    /// Origins to license in pre-licensing mode, `num_replicators` if unset
+    licensed_origin_count: Option<usize>,
+    ploidy: usize,
+    speed_jitter: Option<(SpeedJitter, ChaCha8Rng)>,
+    dntp_budget_per_step: Option<usize>,
+    firing_schedule: Option<FiringSchedule>,
+    interference_distance: usize,
+    minutes_per_iteration: f64,
+    passive_origins: Vec<usize>,
+    /// Replicated fraction after each iteration, when enabled
+    fraction_curve: Option<Vec<f64>>,
+    /// Bin count and per-iteration binned fork counts, when enabled
+    fork_density_map: Option<(usize, Vec<Vec<usize>>)>,
+    /// Groups of origins that fire together, see [`Cell::with_origin_clusters`]
+    origin_clusters: Vec<Vec<usize>>,
+    /// Lesions not yet repaired
+    lesions: Vec<Lesion>,
+    cohesion_sites: Option<CohesionSites>,
+    rescue_events: Vec<(usize, usize)>,
+    fork_restart_prob: f64,
+    fork_restarts: usize,
+    adaptive_step: Option<AdaptiveStep>,
+}
+
+/// Length of human chromosome 1 (GRCh38) in bp
+pub const HUMAN_CHR1_LENGTH: usize = 248_956_422;
+/// Genome length served by each replication machinery, in bp per replicator
+pub const BASES_PER_REPLICATOR: usize = 1_600_000;
+/// Fork speed in bp per step, ~1.5kb/min with a two second step
+pub const DEFAULT_REPLICATION_RATE: usize = 50;
+/// Real duration of one iteration in minutes, the two second step behind
+/// [`DEFAULT_REPLICATION_RATE`]
+pub const DEFAULT_MINUTES_PER_ITERATION: f64 = 2.0 / 60.0;
+/// Probability that a sampled unreplicated position fires as an origin
+pub const DEFAULT_FIRING_PROBABILITY: f64 = 0.1;
+/// Probability of staying in G-phase at each warmup iteration
+pub const DEFAULT_G_PHASE_PROB: f64 = 0.9;
+/// Seed used when a configuration does not give one
+pub const DEFAULT_SEED: u64 = 1701;
+/// Typical eukaryotic Okazaki fragment length in bp
+pub const DEFAULT_OKAZAKI_FRAGMENT_SIZE: usize = 200;
+/// Rejected firing samples allowed before leaving replicators for the next iteration
+const MAX_FIRING_ATTEMPTS: usize = 1_000;
+
+/// Parameters needed to construct and run a [`Cell`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct CellConfig {
+    /// Length of the chromosome in bp
+    pub genome_length: usize,
+    /// Number of replication machineries (origins that can be active at once)
+    pub num_replicators: usize,
+    /// Bases replicated by each fork per step, in bp/step
+    pub replication_rate: usize,
+    pub firing_probability: f64,
+    pub g_phase_prob: f64,
+    /// Seed of the RNG driving a run of this configuration
+    pub seed: u64,
+}
+
+impl CellConfig {
+    /// Human chromosome 1 with one replicator per 1.6Mb
+    pub fn preset_human_chr1() -> Self {
+        CellConfig::new(HUMAN_CHR1_LENGTH, HUMAN_CHR1_LENGTH / BASES_PER_REPLICATOR)
+    }
+    /// Configuration with the default rates, probabilities and seed
+    pub fn new(genome_length: usize, num_replicators: usize) -> Self {
+        CellConfig {
+            genome_length,
+            num_replicators,
+            replication_rate: DEFAULT_REPLICATION_RATE,
+            firing_probability: DEFAULT_FIRING_PROBABILITY,
+            g_phase_prob: DEFAULT_G_PHASE_PROB,
+            seed: DEFAULT_SEED,
+        }
+    }
+    pub fn with_replication_rate(mut self, replication_rate: usize) -> Self {
+        self.replication_rate = replication_rate;
+        self
+    }
+    pub fn with_firing_probability(mut self, firing_probability: f64) -> Self {
+        self.firing_probability = firing_probability;
+        self
+    }
+    pub fn with_g_phase_prob(mut self, g_phase_prob: f64) -> Self {
+        self.g_phase_prob = g_phase_prob;
+        self
+    }
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+}
+
+/// Intra-S checkpoint suppressing origin firing in late zones until enough of the
+/// genome has replicated
+#[derive(Debug, Clone, PartialEq)]
+pub struct CheckpointConfig {
+    /// Checkpoint is lifted once this fraction of the genome is replicated
+    pub active_until_fraction: f64,
+    /// Late replicating zones as half-open ranges of genome coordinates
+    pub late_zones: Vec<Range<usize>>,
+    /// Multiplier on the firing probability inside late zones while active, in (0, 1]
+    pub late_firing_factor: f64,
+}
+
+/// Slow forks down in proportion to how much of the genome just ahead of them is
+/// already replicated
+#[derive(Debug, Clone, PartialEq)]
+pub struct ForkSlowdown {
+    /// Distance ahead of the fork that is inspected, in bp
+    pub window: usize,
+    /// Fraction of the fork speed lost when the whole window is replicated
+    pub factor: f64,
+}
+
+/// Lower the firing probability of candidate origins near live forks, so firing is
+/// suppressed around replicons still growing rather than around fired origins
+#[derive(Debug, Clone, PartialEq)]
+pub struct ForkSuppression {
+    /// Distance either side of a candidate origin searched for active forks, in bp
+    pub window: usize,
+    /// Fraction of the firing probability lost per active fork in the window, with the
+    /// probability floored at zero
+    pub factor: f64,
+}
+
+/// Cohesin-bound sites acting as soft barriers: forks within `window` bp of a site are
+/// slowed but never stopped, biasing termination towards the sites
+#[derive(Debug, Clone, PartialEq)]
+pub struct CohesionSites {
+    pub positions: Vec<usize>,
+    pub window: usize,
+    /// Fraction of the fork speed lost near a site. Slowed forks still move at least
+    /// one base per step.
+    pub factor: f64,
+}
+
+/// Non-biological speed-up for runs where only the final state matters: each fork step
+/// grows with the unreplicated run ahead of it, so large gaps close in a few steps
+#[derive(Debug, Clone, PartialEq)]
+pub struct AdaptiveStep {
+    /// Share of the unreplicated run a fork may replicate in one step
+    pub fraction: f64,
+    /// Largest step, in bp. Steps never fall below the fixed replication rate.
+    pub max_step: usize,
+}
+
+/// Firing probability that changes linearly with the replicated fraction, from
+/// `start_prob` on an unreplicated genome to `end_prob` on a fully replicated one
+#[derive(Debug, Clone, PartialEq)]
+pub struct FiringSchedule {
+    pub start_prob: f64,
+    pub end_prob: f64,
+}
+
+impl FiringSchedule {
+    pub fn probability_at(&self, fraction_replicated: f64) -> f64 {
+        self.start_prob + (self.end_prob - self.start_prob) * fraction_replicated
+    }
+}
+
+/// One fired origin, see [`Cell::firing_table`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct FiringEvent {
+    pub iteration: usize,
+    pub position: usize,
+    /// Index of the replicon the origin seeded, in the order of [`Cell::replicon_sizes`]
+    pub replicon_index: usize,
+}
+
+/// DNA damage that blocks forks at `position` until repaired
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Lesion {
+    pub position: usize,
+    /// Chance per step that a lesion with a fork stalled against it is repaired
+    pub repair_prob: f64,
+}
+
+/// Jitter each fork's speed every step, modelling transient polymerase pausing
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpeedJitter {
+    /// Largest change to a step, in bp. Steps are drawn uniformly from
+    /// `rate - spread..=rate + spread`, clamped at zero.
+    pub spread: usize,
+    /// Seed of the jitter's own RNG, kept apart from firing so runs without jitter
+    /// are unchanged
+    pub seed: u64,
+}
+
+impl CellConfig {
+    /// Check the parameters describe a simulation that can finish
+    pub fn validate(&self) -> Result<(), ReplicationError> {
+        if self.genome_length == 0 {
+            return Err(ReplicationError::InvalidConfig(
+                "genome_length must be positive".to_string(),
+            ));
+        }
+        if self.num_replicators == 0 {
+            return Err(ReplicationError::NoReplicators);
+        }
+        if self.num_replicators > self.genome_length {
+            return Err(ReplicationError::TooManyReplicators {
+                num_replicators: self.num_replicators,
+                genome_length: self.genome_length,
+            });
+        }
+        if self.replication_rate == 0 {
+            return Err(ReplicationError::InvalidConfig(
+                "replication_rate must be positive".to_string(),
+            ));
+        }
+        if !(self.firing_probability > 0.0 && self.firing_probability <= 1.0) {
+            return Err(ReplicationError::InvalidConfig(
+                "firing_probability must be in (0, 1]".to_string(),
+            ));
+        }
+        validate_g_phase_prob(self.g_phase_prob)
+    }
+}
+
+pub(crate) fn validate_g_phase_prob(g_phase_prob: f64) -> Result<(), ReplicationError> {
+    if (0.0..1.0).contains(&g_phase_prob) {
+        Ok(())
+    } else {
+        Err(ReplicationError::InvalidConfig(
+            "g_phase_prob must be in [0, 1)".to_string(),
+        ))
+    }
+}
+
+/// How a cell leaves G-phase and enters S-phase
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GPhaseModel {
+    /// Stay in G-phase at each warmup iteration with this probability, in [0, 1)
+    Stochastic(f64),
+    /// Enter S-phase after exactly this many warmup iterations
+    Fixed(usize),
+}
+
+impl Default for GPhaseModel {
+    fn default() -> Self {
+        GPhaseModel::Stochastic(DEFAULT_G_PHASE_PROB)
+    }
+}
+
+impl From<f64> for GPhaseModel {
+    fn from(g_phase_prob: f64) -> Self {
+        GPhaseModel::Stochastic(g_phase_prob)
+    }
+}
+
+impl GPhaseModel {
+    pub(crate) fn validate(&self) -> Result<(), ReplicationError> {
+        match self {
+            GPhaseModel::Stochastic(g_phase_prob) => validate_g_phase_prob(*g_phase_prob),
+            GPhaseModel::Fixed(_) => Ok(()),
+        }
+    }
+    /// Number of warmup iterations spent in G-phase before entering S-phase
+    pub(crate) fn warmup(&self, rng: &mut ChaCha8Rng) -> usize {
+        match self {
+            GPhaseModel::Stochastic(g_phase_prob) => g_phase_warmup(*g_phase_prob, rng),
+            GPhaseModel::Fixed(warmup_iterations) => *warmup_iterations,
+        }
+    }
+}
+
+/// Number of warmup iterations spent in G-phase before entering S-phase
+fn g_phase_warmup(g_phase_prob: f64, rng: &mut ChaCha8Rng) -> usize {
+    let mut warmup_iterations: usize = 1;
+    while rng.gen::<f64>() <= g_phase_prob {
+        warmup_iterations += 1;
+    }
+    warmup_iterations
+}
+
+impl Default for CellConfig {
+    fn default() -> Self {
+        CellConfig::preset_human_chr1()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReplicationSummary {
+    pub warmup_iterations: usize,
+    pub num_iterations: usize,
+    /// Iterations of the run, from the G1/S transition (or resumed start) to the S/G2
+    /// transition (or stopping point)
+    pub s_phase: Range<usize>,
+    /// Replicators active in each iteration of the run
+    pub utilization: Vec<usize>,
+    /// Real time units of the run's iterations
+    pub clock: Clock,
+    /// RNG state on entering the run, see [`Cell::replay`]
+    pub rng_checkpoint: RngCheckpoint,
+}
+
+/// Exact state of a ChaCha8 RNG, enough to rebuild it and replay a run bit-for-bit
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RngCheckpoint {
+    pub seed: [u8; 32],
+    pub stream: u64,
+    pub word_pos: u128,
+}
+
+impl RngCheckpoint {
+    pub fn capture(rng: &ChaCha8Rng) -> Self {
+        RngCheckpoint {
+            seed: rng.get_seed(),
+            stream: rng.get_stream(),
+            word_pos: rng.get_word_pos(),
+        }
+    }
+    pub fn restore(&self) -> ChaCha8Rng {
+        let mut rng = ChaCha8Rng::from_seed(self.seed);
+        rng.set_stream(self.stream);
+        rng.set_word_pos(self.word_pos);
+        rng
+    }
+}
+
+/// Replication progress of a [`Cell`] part way through S-phase, see [`Cell::snapshot`]
+///
+/// Only the state needed to continue firing and fork movement is kept. Checkpoints,
+/// fork slowdown, custom samplers and tracks must be reapplied to the restored cell.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CellSnapshot {
+    pub genome: Genome,
+    pub num_replicators: usize,
+    pub unassigned_replicators: usize,
+    pub cell_state: CellState,
+    pub iteration: usize,
+    pub replication_rate: usize,
+    pub firing_probability: f64,
+    /// Stream and word position of the RNG driving the run
+    pub rng_stream: u64,
+    pub rng_word_pos: u128,
+}
+
+impl ReplicationSummary {
+    /// Mean number of active replicators per iteration, NaN for an empty run
+    pub fn mean_utilization(&self) -> f64 {
+        mean(&self.utilization)
+    }
+    /// S-phase duration in minutes
+    pub fn s_phase_minutes(&self) -> f64 {
+        self.clock.to_minutes(self.num_iterations)
+    }
+}
+
+impl Cell {
+    /// Unchecked constructor, see [`Cell::from_config`] for a validated one. A cell
+    /// without replicators can never replicate, so running it fails with
+    /// [`ReplicationError::NoReplicators`].
+    pub fn new(genome_length: usize, num_replicators: usize, replication_rate: usize) -> Self {
+        Cell {
+            genome: Genome::new(genome_length, num_replicators),
+            num_replicators,
+            unassigned_replicators: num_replicators,
+            cell_state: CellState::GPhase,
+            replication_rate,
+            firing_probability: DEFAULT_FIRING_PROBABILITY,
+            checkpoint: None,
+            fork_slowdown: None,
+            fork_suppression: None,
+            max_iterations: usize::MAX,
+            iteration: 0,
+            firing_log: Vec::new(),
+            termination_sites: Vec::new(),
+            lagging_strand_bases: 0,
+            okazaki_fragment_size: DEFAULT_OKAZAKI_FRAGMENT_SIZE,
+            #[cfg(feature = "std")]
+            timing_track: None,
+            firing_sampler: Box::new(LengthWeightedSampler::default()),
+            refire_rejections: 0,
+            utilization: Vec::new(),
+            licensing: LicensingMode::Continuous,
+            licensed_origins: None,
+            licensed_origin_count: None,
+            ploidy: 2,
+            speed_jitter: None,
+            dntp_budget_per_step: None,
+            firing_schedule: None,
+            interference_distance: 0,
+            minutes_per_iteration: DEFAULT_MINUTES_PER_ITERATION,
+            passive_origins: Vec::new(),
+            fraction_curve: None,
+            fork_density_map: None,
+            origin_clusters: Vec::new(),
+            lesions: Vec::new(),
+            cohesion_sites: None,
+            rescue_events: Vec::new(),
+            fork_restart_prob: 0.0,
+            fork_restarts: 0,
+            adaptive_step: None,
+        }
+    }
+    pub fn preset_human_chr1() -> Self {
+        let config = CellConfig::preset_human_chr1();
+        Cell::new(
+            config.genome_length,
+            config.num_replicators,
+            config.replication_rate,
+        )
+        .with_firing_probability(config.firing_probability)
+    }
+    pub fn from_config(config: &CellConfig) -> Result<Self, ReplicationError> {
+        config.validate()?;
+        Ok(Cell::new(
+            config.genome_length,
+            config.num_replicators,
+            config.replication_rate,
+        )
+        .with_firing_probability(config.firing_probability))
+    }
+    pub fn with_firing_probability(mut self, firing_probability: f64) -> Self {
+        self.firing_probability = firing_probability;
+        self
+    }
+    /// Vary the firing probability over S-phase, replacing the fixed probability at the
+    /// start of every assignment
+    pub fn with_firing_schedule(mut self, firing_schedule: FiringSchedule) -> Self {
+        self.firing_schedule = Some(firing_schedule);
+        self
+    }
+    /// Reject candidate origins closer than `interference_distance` to an origin already
+    /// fired this S-phase
+    pub fn with_interference_distance(mut self, interference_distance: usize) -> Self {
+        self.interference_distance = interference_distance;
+        self
+    }
+    /// Fire origins in coordinated groups: when any member of a cluster fires, its
+    /// unreplicated partners fire in the same iteration while replicators are free.
+    /// Partners skip the probability check but still respect the interference distance.
+    pub fn with_origin_clusters(mut self, origin_clusters: Vec<Vec<usize>>) -> Self {
+        self.origin_clusters = origin_clusters;
+        self
+    }
+    pub fn with_checkpoint(mut self, checkpoint: CheckpointConfig) -> Self {
+        self.checkpoint = Some(checkpoint);
+        self
+    }
+    /// Weight origin licensing by region, see [`Genome::with_density_profile`]
+    pub fn with_density_profile(mut self, density_profile: Vec<(Range<usize>, f64)>) -> Self {
+        self.genome = self.genome.with_density_profile(density_profile);
+        self
+    }
+    /// Limit origin licensing resolution, see [`Genome::with_min_replicon_size`]
+    pub fn with_min_replicon_size(mut self, min_replicon_size: usize) -> Self {
+        self.genome = self.genome.with_min_replicon_size(min_replicon_size);
+        self
+    }
+    /// Forbid origins in heterochromatin, see [`Genome::with_heterochromatin`]
+    pub fn with_heterochromatin(mut self, heterochromatin: Vec<Range<usize>>) -> Self {
+        self.genome = self.genome.with_heterochromatin(heterochromatin);
+        self
+    }
+    /// Group origins into replication domains, see [`Genome::with_domains`]
+    pub fn with_domains(mut self, domains: Vec<ReplicationDomain>) -> Self {
+        self.genome = self.genome.with_domains(domains);
+        self
+    }
+    pub fn with_fork_slowdown(mut self, fork_slowdown: ForkSlowdown) -> Self {
+        self.fork_slowdown = Some(fork_slowdown);
+        self
+    }
+    /// Suppress firing near active forks. Unlike [`Cell::with_interference_distance`]
+    /// the suppression lifts once the forks have moved on or merged.
+    pub fn with_fork_suppression(mut self, fork_suppression: ForkSuppression) -> Self {
+        self.fork_suppression = Some(fork_suppression);
+        self
+    }
+    /// Scale fork steps with the size of the gap ahead instead of replicating at the
+    /// fixed rate. Timing readouts are then meaningless, only the final state is.
+    pub fn with_adaptive_step(mut self, adaptive_step: AdaptiveStep) -> Self {
+        self.adaptive_step = Some(adaptive_step);
+        self
+    }
+    /// Leave telomeres unreplicated, see [`Genome::with_telomere_length`]
+    pub fn with_telomere_length(mut self, telomere_length: usize) -> Self {
+        self.genome = self.genome.with_telomere_length(telomere_length);
+        self
+    }
+    /// Stall the first fork to reach each lesion until it is repaired. The converging
+    /// fork from the other side replicates through a lesion with a fork stalled against
+    /// it, rescuing the stalled fork. Origins do not fire on an unrepaired lesion, and
+    /// lesions never repaired or rescued are left as unreplicated gaps.
+    pub fn with_lesions(mut self, lesions: Vec<Lesion>) -> Self {
+        self.lesions = lesions;
+        self
+    }
+    /// Chance per step that a fork stalled at a lesion restarts past it, replicating the
+    /// lesion and clearing it as repair would. Restarts are counted separately.
+    pub fn with_fork_restart_prob(mut self, fork_restart_prob: f64) -> Self {
+        self.fork_restart_prob = fork_restart_prob;
+        self
+    }
+    /// Stalled forks that restarted past their lesion so far
+    pub fn fork_restarts(&self) -> usize {
+        self.fork_restarts
+    }
+    /// Slow forks near cohesion sites, see [`CohesionSites`]
+    pub fn with_cohesion_sites(mut self, cohesion_sites: CohesionSites) -> Self {
+        self.cohesion_sites = Some(cohesion_sites);
+        self
+    }
+    /// Lesions still blocking replication
+    pub fn lesions(&self) -> &[Lesion] {
+        &self.lesions
+    }
+    /// Lesions replicated through by a converging fork, as (iteration, position) pairs
+    /// in the order the stalled forks were rescued
+    pub fn rescue_events(&self) -> &[(usize, usize)] {
+        &self.rescue_events
+    }
+    /// Keep origins away from the chromosome ends, see [`Genome::with_terminal_exclusion`]
+    pub fn with_terminal_exclusion(mut self, terminal_exclusion: usize) -> Self {
+        self.genome = self.genome.with_terminal_exclusion(terminal_exclusion);
+        self
+    }
+    pub fn with_speed_jitter(mut self, speed_jitter: SpeedJitter) -> Self {
+        let rng = ChaCha8Rng::seed_from_u64(speed_jitter.seed);
+        self.speed_jitter = Some((speed_jitter, rng));
+        self
+    }
+    /// Cap the bases replicated by all forks together in one step, shared between forks
+    /// in proportion to their demand when it binds. A zero budget can never finish, so
+    /// runs fail with [`ReplicationError::InvalidConfig`].
+    pub fn with_dntp_budget(mut self, dntp_budget_per_step: usize) -> Self {
+        self.dntp_budget_per_step = Some(dntp_budget_per_step);
+        self
+    }
+    /// Export absolute coordinates, see [`Genome::with_coordinate_offset`]
+    pub fn with_coordinate_offset(mut self, coordinate_offset: usize) -> Self {
+        self.genome = self.genome.with_coordinate_offset(coordinate_offset);
+        #[cfg(feature = "std")]
+        {
+            self.timing_track = self
+                .timing_track
+                .map(|track| track.with_coordinate_offset(coordinate_offset));
+        }
+        self
+    }
+    /// Divide the chromosome into arms, see [`Genome::with_centromere`]
+    pub fn with_centromere(mut self, centromere: usize, barrier: bool) -> Self {
+        self.genome = self.genome.with_centromere(centromere, barrier);
+        self
+    }
+    /// Length in bp of each Okazaki fragment
+    pub fn with_okazaki_fragment_size(mut self, okazaki_fragment_size: NonZeroUsize) -> Self {
+        self.okazaki_fragment_size = okazaki_fragment_size.get();
+        self
+    }
+    /// Stop replication runs after this many S-phase iterations even if unfinished
+    pub fn with_max_iterations(mut self, max_iterations: usize) -> Self {
+        self.max_iterations = max_iterations;
+        self
+    }
+    /// Record the iteration each base replicates in, costing one entry per base
+    #[cfg(feature = "std")]
+    pub fn with_timing_track(mut self) -> Self {
+        self.timing_track = Some(
+            TimingTrack::new(self.genome.genome_length)
+                .with_coordinate_offset(self.genome.coordinate_offset()),
+        );
+        self
+    }
+    /// Rebuild a cell from `snapshot`, with an RNG seeded from `rng_seed` and moved to
+    /// the snapshot's position so that the run continues as if never interrupted
+    pub fn from_snapshot(snapshot: CellSnapshot, rng_seed: u64) -> (Self, ChaCha8Rng) {
+        let mut rng = ChaCha8Rng::seed_from_u64(rng_seed);
+        rng.set_stream(snapshot.rng_stream);
+        rng.set_word_pos(snapshot.rng_word_pos);
+        let genome_length = snapshot.genome.genome_length;
+        let cell = Cell {
+            genome: snapshot.genome,
+            unassigned_replicators: snapshot.unassigned_replicators,
+            cell_state: snapshot.cell_state,
+            iteration: snapshot.iteration,
+            firing_probability: snapshot.firing_probability,
+            ..Cell::new(
+                genome_length,
+                snapshot.num_replicators,
+                snapshot.replication_rate,
+            )
+        };
+        (cell, rng)
+    }
+    /// Capture the current state along with the position of the `rng` driving the run
+    pub fn snapshot(&self, rng: &ChaCha8Rng) -> CellSnapshot {
+        CellSnapshot {
+            genome: self.genome.clone(),
+            num_replicators: self.num_replicators,
+            unassigned_replicators: self.unassigned_replicators,
+            cell_state: self.cell_state.clone(),
+            iteration: self.iteration,
+            replication_rate: self.replication_rate,
+            firing_probability: self.firing_probability,
+            rng_stream: rng.get_stream(),
+            rng_word_pos: rng.get_word_pos(),
+        }
+    }
+    pub fn with_licensing(mut self, licensing: LicensingMode) -> Self {
+        self.licensing = licensing;
+        self
+    }
+    /// License `licensed_origin_count` origins in pre-licensing mode, setting the
+    /// licensing density independently of the replicator pool and activation rate
+    pub fn with_licensed_origin_count(mut self, licensed_origin_count: usize) -> Self {
+        self.licensed_origin_count = Some(licensed_origin_count);
+        self
+    }
+    /// Pre-license `licensed_origins` instead of sampling them at the start of S-phase,
+    /// e.g. to share one origin set between cells
+    pub(crate) fn with_licensed_origins(mut self, licensed_origins: Vec<usize>) -> Self {
+        self.licensing = LicensingMode::PreLicense;
+        self.licensed_origins = Some(licensed_origins);
+        self
+    }
+    /// Licensed origins that have not yet fired, `None` before licensing has happened
+    pub fn licensed_origins(&self) -> Option<&[usize]> {
+        self.licensed_origins.as_deref()
+    }
+    /// Licensed origins found already replicated when they next tried to fire, in the
+    /// order they were found
+    pub fn passive_origins(&self) -> &[usize] {
+        &self.passive_origins
+    }
+    /// Choose origin candidates with `sampler` instead of the default [`LengthWeightedSampler`]
+    pub fn with_firing_sampler(mut self, sampler: impl FiringSampler + 'static) -> Self {
+        self.firing_sampler = Box::new(sampler);
+        self
+    }
+    /// Record the replicated fraction after every iteration
+    pub fn with_fraction_curve(mut self) -> Self {
+        self.fraction_curve = Some(Vec::new());
+        self
+    }
+    pub fn fraction_curve(&self) -> Option<&[f64]> {
+        self.fraction_curve.as_deref()
+    }
+    /// Record the forks in each of `n_bins` genomic bins after every iteration, giving a
+    /// space-time map of fork density, see [`Genome::binned_fork_count`]
+    pub fn with_fork_density_map(mut self, n_bins: usize) -> Self {
+        self.fork_density_map = Some((n_bins, Vec::new()));
+        self
+    }
+    /// Binned fork counts indexed by iteration, then bin
+    pub fn fork_density_map(&self) -> Option<&[Vec<usize>]> {
+        self.fork_density_map
+            .as_ref()
+            .map(|(_, map)| map.as_slice())
+    }
+    /// Real duration of one iteration in minutes
+    pub fn with_minutes_per_iteration(mut self, minutes_per_iteration: f64) -> Self {
+        self.minutes_per_iteration = minutes_per_iteration;
+        self
+    }
+    /// Conversion from this cell's iterations to minutes and kb/min
+    pub fn clock(&self) -> Clock {
+        Clock {
+            minutes_per_iteration: self.minutes_per_iteration,
+            bp_per_iteration: self.replication_rate,
+        }
+    }
+    /// S-phase iterations run so far
+    pub fn iteration(&self) -> usize {
+        self.iteration
+    }
+    pub fn genome(&self) -> &Genome {
+        &self.genome
+    }
+    /// Origins fired so far as (iteration, position) pairs, in firing order
+    pub fn firing_log(&self) -> &[(usize, usize)] {
+        &self.firing_log
+    }
+    /// Sampled firing positions rejected because they were already replicated
+    pub fn refire_rejections(&self) -> usize {
+        self.refire_rejections
+    }
+    /// Positions where converging forks met, in the order the merges happened
+    pub fn termination_sites(&self) -> &[usize] {
+        &self.termination_sites
+    }
+    /// Origins that actively fired, one per replicon. Licensed origins that were
+    /// passively replicated by a neighbour's forks are not counted.
+    pub fn replicon_count(&self) -> usize {
+        self.firing_log.len()
+    }
+    /// Fork merges so far. Once a linear chromosome is fully replicated this is one
+    /// fewer than the number of origins fired.
+    pub fn total_merges(&self) -> usize {
+        self.termination_sites.len()
+    }
+    /// Fired origins in firing order, each linked to the replicon it seeded
+    pub fn firing_table(&self) -> Vec<FiringEvent> {
+        let mut origins: Vec<usize> = self
+            .firing_log
+            .iter()
+            .map(|(_, position)| *position)
+            .collect();
+        origins.sort_unstable();
+        self.firing_log
+            .iter()
+            .map(|&(iteration, position)| FiringEvent {
+                iteration,
+                position,
+                replicon_index: origins.partition_point(|&origin| origin < position),
+            })
+            .collect()
+    }
+    /// Distances between neighbouring fired origins
+    pub fn inter_origin_distances(&self) -> Vec<usize> {
+        let mut origins: Vec<usize> = self
+            .firing_log
+            .iter()
+            .map(|(_, position)| *position)
+            .collect();
+        origins.sort_unstable();
+        origins.windows(2).map(|pair| pair[1] - pair[0]).collect()
+    }
+    /// Iterations between consecutive origin firings, in firing order. Origins fired
+    /// in the same step are 0 apart.
+    pub fn inter_firing_times(&self) -> Vec<usize> {
+        let mut iterations: Vec<usize> = self
+            .firing_log
+            .iter()
+            .map(|(iteration, _)| *iteration)
+            .collect();
+        iterations.sort_unstable();
+        iterations
+            .windows(2)
+            .map(|pair| pair[1] - pair[0])
+            .collect()
+    }
+    /// Mean of [`Cell::inter_firing_times`], NaN with fewer than two firings
+    pub fn mean_inter_firing_time(&self) -> f64 {
+        mean(&self.inter_firing_times())
+    }
+    /// Sample variance of [`Cell::inter_firing_times`], NaN with fewer than three firings
+    pub fn inter_firing_time_variance(&self) -> f64 {
+        variance(&self.inter_firing_times())
+    }
+    /// Lengths of the territories replicated by each fired origin, from the genome start.
+    /// Each territory lies between consecutive termination sites, so this is only
+    /// complete once the genome is fully replicated.
+    pub fn replicon_sizes(&self) -> Vec<usize> {
+        let mut boundaries = self.termination_sites.clone();
+        boundaries.sort_unstable();
+        boundaries.insert(0, 0);
+        boundaries.push(self.genome.genome_length);
+        boundaries
+            .windows(2)
+            .map(|pair| pair[1] - pair[0])
+            .collect()
+    }
+    /// Distance each fired origin's forks have travelled, as `(origin, left_distance,
+    /// right_distance)` from the genome start: the replicated run holding the origin,
+    /// cut at the termination sites either side. The origin base counts on the right.
+    /// Once the genome is fully replicated without telomeres, the two distances sum to
+    /// the origin's [`Cell::replicon_sizes`] entry. After
+    /// [`Cell::endoreduplicate`] only the last round's origins and sites are used.
+    pub fn fork_travel_distances(&self) -> Vec<(usize, usize, usize)> {
+        let mut origins: Vec<usize> = self
+            .firing_log
+            .iter()
+            .map(|(_, position)| *position)
+            .collect();
+        origins.sort_unstable();
+        let mut sites = self.termination_sites.clone();
+        sites.sort_unstable();
+        let replicated = self.genome.replicated_intervals();
+        origins
+            .into_iter()
+            .map(|origin| {
+                let run = &replicated[replicated.partition_point(|run| run.end <= origin)];
+                let site = sites.partition_point(|&site| site <= origin);
+                let start = run
+                    .start
+                    .max(site.checked_sub(1).map_or(0, |left| sites[left]));
+                let end = run.end.min(sites.get(site).copied().unwrap_or(usize::MAX));
+                (origin, origin - start, end - origin)
+            })
+            .collect()
+    }
+    /// Origin firing efficiency backed out of the replicon sizes alone: the theoretical
+    /// spacing `genome_length / num_replicators` over the mean replicon size. On a fully
+    /// replicated genome each fired origin seeds one replicon, so this is the number of
+    /// origins fired per replicator, above 1 when freed replicators fire again. With
+    /// [`LicensingMode::PreLicense`] and one licensed origin per replicator it is the
+    /// fraction of licensed origins that fired.
+    pub fn estimate_efficiency_from_replicons(&self) -> f64 {
+        let spacing = self.genome.genome_length as f64 / self.num_replicators as f64;
+        spacing / mean(&self.replicon_sizes())
+    }
+    /// Estimated Okazaki fragments synthesised so far. Every fork step copies its
+    /// distance once on the lagging strand, whichever direction the fork runs.
+    pub fn okazaki_fragment_count(&self) -> usize {
+        self.lagging_strand_bases / self.okazaki_fragment_size
+    }
+    pub fn inter_origin_histogram(
+        &self,
+        bin_width: usize,
+    ) -> Result<Vec<(usize, usize)>, ReplicationError> {
+        histogram(&self.inter_origin_distances(), bin_width)
+    }
+    pub fn replicon_size_histogram(
+        &self,
+        bin_width: usize,
+    ) -> Result<Vec<(usize, usize)>, ReplicationError> {
+        histogram(&self.replicon_sizes(), bin_width)
+    }
+    /// Number of termination sites in each of `n_bins` equal bins. A site on a bin
+    /// boundary counts towards the bin starting there.
+    pub fn termination_density(&self, n_bins: usize) -> Result<Vec<usize>, ReplicationError> {
+        if n_bins == 0 {
+            return Err(ReplicationError::InvalidConfig(
+                "n_bins must be positive".to_string(),
+            ));
+        }
+        let genome_length = self.genome.genome_length;
+        let bin_starts: Vec<usize> = (0..n_bins)
+            .map(|bin| bin * genome_length / n_bins)
+            .collect();
+        let mut counts = vec![0; n_bins];
+        for site in &self.termination_sites {
+            let bin = bin_starts.partition_point(|&start| start <= *site);
+            if bin > 0 {
+                counts[bin - 1] += 1;
+            }
+        }
+        Ok(counts)
+    }
+    #[cfg(feature = "std")]
+    pub fn timing_track(&self) -> Option<&TimingTrack> {
+        self.timing_track.as_ref()
+    }
+    pub fn is_fully_replicated(&self) -> bool {
+        self.genome.is_fully_replicated()
+    }
+    fn firing_probability_at(
+        &self,
+        base_probability: f64,
+        position: usize,
+        checkpoint_active: bool,
+        late_domains_open: bool,
+    ) -> f64 {
+        let mut firing_probability = base_probability * self.genome.origin_density_at(position);
+        if let Some(domain) = self.genome.domain_at(position) {
+            if domain.timing == DomainTiming::Late && !late_domains_open {
+                return 0.0;
+            }
+            firing_probability *= domain.firing_multiplier;
+        }
+        if let Some(suppression) = &self.fork_suppression {
+            let nearby_forks = self
+                .genome
+                .fork_positions()
+                .into_iter()
+                .filter(|fork| fork.abs_diff(position) <= suppression.window)
+                .count();
+            firing_probability *= (1.0 - suppression.factor * nearby_forks as f64).max(0.0);
+        }
+        match &self.checkpoint {
+            Some(checkpoint)
+                if checkpoint_active
+                    && checkpoint
+                        .late_zones
+                        .iter()
+                        .any(|zone| zone.contains(&position)) =>
+            {
+                firing_probability * checkpoint.late_firing_factor
+            }
+            _ => firing_probability,
+        }
+    }
+    /// Fire unassigned replicators at unreplicated positions. Replicators that cannot be
+    /// placed this iteration stay unassigned; calling this on a fully replicated genome
+    /// is an error.
+    pub fn assign_replicators(&mut self, rng_obj: &mut ChaCha8Rng) -> Result<(), ReplicationError> {
+        if self.genome.is_fully_replicated() {
+            return Err(ReplicationError::NoUnreplicatedRegions);
+        }
+        let checkpoint_active = self.checkpoint.as_ref().is_some_and(|checkpoint| {
+            self.genome.fraction_replicated() < checkpoint.active_until_fraction
+        });
+        let late_domains_open = self.genome.early_domains_replicated();
+        // A schedule replaces the configured probability for this step only
+        let base_probability = match &self.firing_schedule {
+            Some(schedule) => schedule.probability_at(self.genome.fraction_replicated()),
+            None => self.firing_probability,
+        };
+        if self.licensing == LicensingMode::PreLicense {
+            self.fire_licensed_origins(
+                base_probability,
+                checkpoint_active,
+                late_domains_open,
+                rng_obj,
+            );
+            return Ok(());
+        }
+        // Regions that could take another origin, kept up to date as origins are placed
+        // so leftover replicators wait for the next step once none remain
+        let mut hostable_regions = self.genome.hostable_region_count();
+        // If there are unassigned replicators, assign them
+        while self.unassigned_replicators > 0 && hostable_regions > 0 {
+            self.firing_sampler.prepare(&self.genome);
+            // Sample candidate positions until one passes the firing check
+            let mut position: isize = -1;
+            let mut attempts: usize = 0;
+            while position < 0 {
+                if attempts == MAX_FIRING_ATTEMPTS {
+                    return Ok(());
+                }
+                attempts += 1;
+                let Some(genome_position) = self.firing_sampler.sample(&self.genome, rng_obj)
+                else {
+                    return Ok(());
+                };
+                // Each origin fires at most once per S-phase, so replicated positions are rejected
+                if self.genome.is_replicated(genome_position) != Ok(false) {
+                    self.refire_rejections += 1;
+                    continue;
+                }
+                if self.interferes_with_fired_origin(genome_position)
+                    || self.is_unrepaired_lesion(genome_position)
+                    || self.genome.in_too_small_region(genome_position)
+                {
+                    continue;
+                }
+                // Random chance check if this position can be used
+                if rng_obj.gen::<f64>()
+                    < self.firing_probability_at(
+                        base_probability,
+                        genome_position,
+                        checkpoint_active,
+                        late_domains_open,
+                    )
+                {
+                    position = genome_position as isize;
+                };
+            }
+            let position = position as usize;
+            let index = self.genome.run_index(position);
+            let run_start = self.genome.cumsum[index] - self.genome.replication_state[index];
+            let run_end = self.genome.cumsum[index];
+            self.fire_origin(position);
+            hostable_regions = if self.origin_clusters.is_empty() {
+                // The host run is split into the stretches either side of the origin
+                hostable_regions - 1
+                    + usize::from(self.genome.can_host_origin(position - run_start))
+                    + usize::from(self.genome.can_host_origin(run_end - 1 - position))
+            } else {
+                self.genome.hostable_region_count()
+            };
+            debug_assert_eq!(hostable_regions, self.genome.hostable_region_count());
+        }
+        Ok(())
+    }
+    /// Whether `position` lies within the interference distance of an origin fired
+    /// this S-phase
+    fn interferes_with_fired_origin(&self, position: usize) -> bool {
+        self.interference_distance > 0
+            && self
+                .firing_log
+                .iter()
+                .any(|(_, fired)| fired.abs_diff(position) < self.interference_distance)
+    }
+    fn is_unrepaired_lesion(&self, position: usize) -> bool {
+        self.lesions
+            .iter()
+            .any(|lesion| lesion.position == position)
+    }
+    /// Whether a fork moving right (`rightward`) or left is stalled against `lesion`
+    fn stalls_fork(&self, lesion: &Lesion, rightward: bool) -> bool {
+        if rightward {
+            lesion.position > 0 && self.genome.is_replicated(lesion.position - 1) == Ok(true)
+        } else {
+            self.genome.is_replicated(lesion.position + 1) == Ok(true)
+        }
+    }
+    /// Give each lesion with a fork stalled against it a chance to be repaired, then give
+    /// each stalled fork still blocked a chance to restart past its lesion
+    fn repair_lesions(&mut self, rng: &mut ChaCha8Rng) {
+        let stalled: Vec<bool> = self
+            .lesions
+            .iter()
+            .map(|lesion| self.stalls_fork(lesion, true) || self.stalls_fork(lesion, false))
+            .collect();
+        let mut stalled = stalled.into_iter();
+        let (restart_prob, fork_restarts) = (self.fork_restart_prob, &mut self.fork_restarts);
+        self.lesions.retain(|lesion| {
+            if stalled.next() != Some(true) {
+                return true;
+            }
+            if rng.gen::<f64>() < lesion.repair_prob {
+                return false;
+            }
+            // Only draw for restarts when enabled, so runs without them are unchanged
+            let restarted = restart_prob > 0.0 && rng.gen::<f64>() < restart_prob;
+            *fork_restarts += usize::from(restarted);
+            !restarted
+        });
+    }
+    /// Record lesions a converging fork has replicated through and drop them
+    fn record_rescues(&mut self) {
+        let genome = &self.genome;
+        let (iteration, rescue_events) = (self.iteration, &mut self.rescue_events);
+        self.lesions.retain(|lesion| {
+            let rescued = genome.is_replicated(lesion.position) == Ok(true);
+            if rescued {
+                rescue_events.push((iteration, lesion.position));
+            }
+            !rescued
+        });
+    }
+    /// Most bases a fork at `fork` can replicate before reaching an unrepaired lesion,
+    /// moving right if `rightward` and left otherwise. Lesions already stalling a fork
+    /// from the other side do not block.
+    fn lesion_limit(&self, fork: usize, rightward: bool) -> usize {
+        self.lesions
+            .iter()
+            .filter(|lesion| !self.stalls_fork(lesion, !rightward))
+            .filter_map(|lesion| {
+                if rightward {
+                    lesion.position.checked_sub(fork)
+                } else {
+                    fork.checked_sub(lesion.position + 1)
+                }
+            })
+            .min()
+            .unwrap_or(usize::MAX)
+    }
+    /// Start a replicator at the unreplicated `position`, then at its cluster partners
+    fn fire_origin(&mut self, position: usize) {
+        self.start_replicator(position);
+        let partners: Vec<usize> = self
+            .origin_clusters
+            .iter()
+            .filter(|cluster| cluster.contains(&position))
+            .flatten()
+            .copied()
+            .filter(|&partner| partner != position)
+            .collect();
+        for partner in partners {
+            if self.unassigned_replicators == 0 {
+                break;
+            }
+            if self.genome.is_replicated(partner) == Ok(false)
+                && !self.interferes_with_fired_origin(partner)
+            {
+                self.start_replicator(partner);
+            }
+        }
+    }
+    fn start_replicator(&mut self, position: usize) {
+        self.firing_log.push((self.iteration, position));
+
+        let insert_index = self.genome.run_index(position);
+        self.genome.insert_origin(insert_index, position);
+
+        // Update number of repliactors
+        self.unassigned_replicators -= 1;
+    }
+    /// Choose the licensed number of distinct unreplicated positions with the firing
+    /// sampler
+    pub(crate) fn license_origins(&mut self, rng: &mut ChaCha8Rng) -> Vec<usize> {
+        self.firing_sampler.prepare(&self.genome);
+        let target = self.licensed_origin_count.unwrap_or(self.num_replicators);
+        let mut licensed: Vec<usize> = Vec::with_capacity(target);
+        let mut attempts: usize = 0;
+        // Every licensed origin takes one sample, on top of the allowed rejections
+        while licensed.len() < target && attempts < MAX_FIRING_ATTEMPTS + target {
+            attempts += 1;
+            match self.firing_sampler.sample(&self.genome, rng) {
+                Some(position)
+                    if self.genome.is_replicated(position) == Ok(false)
+                        && !self.genome.in_too_small_region(position)
+                        && !licensed.contains(&position) =>
+                {
+                    licensed.push(position)
+                }
+                Some(_) => {}
+                None => break,
+            }
+        }
+        licensed.sort_unstable();
+        licensed
+    }
+    /// Give each licensed origin that is still unreplicated one chance to fire, while
+    /// replicators are free, unless it lies within the interference distance of an origin
+    /// already fired, on an unrepaired lesion or in a run that forks have shrunk below
+    /// the minimum replicon size. Passively replicated origins are dropped.
+    fn fire_licensed_origins(
+        &mut self,
+        base_probability: f64,
+        checkpoint_active: bool,
+        late_domains_open: bool,
+        rng: &mut ChaCha8Rng,
+    ) {
+        let licensed = match self.licensed_origins.take() {
+            Some(licensed) => licensed,
+            None => self.license_origins(rng),
+        };
+        let mut remaining: Vec<usize> = Vec::with_capacity(licensed.len());
+        for position in licensed {
+            if self.genome.is_replicated(position) != Ok(false) {
+                self.passive_origins.push(position);
+                continue;
+            }
+            if self.unassigned_replicators > 0
+                && !self.interferes_with_fired_origin(position)
+                && !self.is_unrepaired_lesion(position)
+                && !self.genome.in_too_small_region(position)
+                && rng.gen::<f64>()
+                    < self.firing_probability_at(
+                        base_probability,
+                        position,
+                        checkpoint_active,
+                        late_domains_open,
+                    )
+            {
+                self.fire_origin(position);
+            } else {
+                remaining.push(position);
+            }
+        }
+        self.licensed_origins = Some(remaining);
+    }
+    /// Bases a fork replicates this step, reduced by any configured slowdown according to
+    /// the replicated density of the window `ahead` of it, then jittered if configured
+    fn fork_step(&mut self, replicated: &[Range<usize>], ahead: Range<usize>) -> usize {
+        let step = self.slowed_step(replicated, ahead);
+        match &mut self.speed_jitter {
+            Some((jitter, rng)) => {
+                let spread = jitter.spread as isize;
+                (step as isize + rng.gen_range(-spread..=spread)).max(0) as usize
+            }
+            None => step,
+        }
+    }
+    /// Slow `step` for a fork at `fork` near a cohesion site, without stopping a fork
+    /// that would otherwise move
+    fn cohesion_step(&self, fork: usize, step: usize) -> usize {
+        match &self.cohesion_sites {
+            Some(cohesion)
+                if cohesion
+                    .positions
+                    .iter()
+                    .any(|site| site.abs_diff(fork) <= cohesion.window) =>
+            {
+                ((step as f64 * (1.0 - cohesion.factor)) as usize).max(step.min(1))
+            }
+            _ => step,
+        }
+    }
+    /// Grow `step` with the `available` bases ahead when adaptive stepping is on
+    fn adaptive(&self, step: usize, available: usize) -> usize {
+        match &self.adaptive_step {
+            Some(adaptive) => {
+                let scaled = (available as f64 * adaptive.fraction) as usize;
+                step.max(scaled.min(adaptive.max_step))
+            }
+            None => step,
+        }
+    }
+    /// Bases all forks would replicate this step at the base rate with no budget
+    fn fork_demand(&self) -> usize {
+        let state = &self.genome.replication_state;
+        (1..state.len() - 1)
+            .step_by(2)
+            .map(|index| {
+                let mut available =
+                    state[index].saturating_sub(self.genome.telomere_reserve(index));
+                let mut demand = 0;
+                for occupied in [state[index - 1] > 0, state[index + 1] > 0] {
+                    if occupied {
+                        let desired = available.min(self.replication_rate);
+                        demand += desired;
+                        available -= desired;
+                    }
+                }
+                demand
+            })
+            .sum()
+    }
+    fn slowed_step(&self, replicated: &[Range<usize>], ahead: Range<usize>) -> usize {
+        let Some(slowdown) = &self.fork_slowdown else {
+            return self.replication_rate;
+        };
+        let overlap: usize = replicated
+            .iter()
+            .map(|interval| {
+                interval
+                    .end
+                    .min(ahead.end)
+                    .saturating_sub(interval.start.max(ahead.start))
+            })
+            .sum();
+        let density = overlap as f64 / ahead.len().max(1) as f64;
+        let slowed = self.replication_rate as f64 * (1.0 - slowdown.factor * density);
+        // Round half up by hand, as `f64::round` needs std
+        ((slowed + 0.5) as usize).max(1)
+    }
+    pub fn replicate_and_merge(&mut self) {
+        let num_entries = self.genome.replication_state.len();
+        // Slowdown is judged against the state at the start of the step
+        let replicated = match &self.fork_slowdown {
+            Some(_) => self.genome.replicated_intervals(),
+            None => Vec::new(),
+        };
+        let window = self
+            .fork_slowdown
+            .as_ref()
+            .map_or(0, |slowdown| slowdown.window);
+
+        // Under a dNTP budget each fork gets a share in proportion to its unlimited demand
+        let budget = self.dntp_budget_per_step.unwrap_or(usize::MAX);
+        let demand = self.dntp_budget_per_step.map_or(0, |_| self.fork_demand());
+        let mut remaining_budget = budget;
+        let allotment = |desired: usize| -> usize {
+            if demand > budget {
+                (desired * budget / demand).max(1)
+            } else {
+                usize::MAX
+            }
+        };
+
+        for index in (1..(num_entries - 1)).step_by(2).rev() {
+            // At each unreplicated region, give one of the values to
+            // adjacent occupied replication regions
+            let left_occupied = self.genome.replication_state[index - 1] > 0;
+            let right_occupied = self.genome.replication_state[index + 1] > 0;
+            // Forks running into a chromosome end stop short of the telomere
+            let reserved = self.genome.telomere_reserve(index);
+
+            if self.genome.replication_state[index] > reserved {
+                if left_occupied {
+                    let fork = self.genome.cumsum[index] - self.genome.replication_state[index];
+                    let ahead = fork..(fork + window).min(self.genome.genome_length);
+                    let available = self.genome.replication_state[index] - reserved;
+                    let step = self.fork_step(&replicated, ahead);
+                    let step = self.cohesion_step(fork, step);
+                    let move_amount = available
+                        .min(self.adaptive(step, available))
+                        .min(self.genome.barrier_limit(fork, true))
+                        .min(self.lesion_limit(fork, true))
+                        .min(allotment(available.min(self.replication_rate)))
+                        .min(remaining_budget);
+                    self.genome.transfer(index, index - 1, move_amount);
+                    self.lagging_strand_bases += move_amount;
+                    remaining_budget -= move_amount;
+                }
+                if (right_occupied) && (self.genome.replication_state[index] > reserved) {
+                    let fork = self.genome.cumsum[index];
+                    let ahead = fork.saturating_sub(window)..fork;
+                    let available = self.genome.replication_state[index] - reserved;
+                    let step = self.fork_step(&replicated, ahead);
+                    let step = self.cohesion_step(fork, step);
+                    let move_amount = available
+                        .min(self.adaptive(step, available))
+                        .min(self.genome.barrier_limit(fork, false))
+                        .min(self.lesion_limit(fork, false))
+                        .min(allotment(available.min(self.replication_rate)))
+                        .min(remaining_budget);
+                    self.genome.transfer(index, index + 1, move_amount);
+                    self.lagging_strand_bases += move_amount;
+                    remaining_budget -= move_amount;
+                }
+            }
+
+            // Merge if now 0 and both neighbours are occupied
+            if (self.genome.replication_state[index] == 0) && left_occupied && right_occupied {
+                self.termination_sites.push(self.genome.cumsum[index - 1]);
+                self.genome.merge_across(index);
+
+                // Count the merge
+                self.unassigned_replicators += 1;
+            }
+        }
+        // Edge case for merging genome start. Only the empty runs are dropped: the
+        // replicon that reached the start still holds its replicator, which is freed
+        // when its other fork merges, so no replicator returns to the pool here.
+        if (self.genome.replication_state[0] == 0) && (self.genome.replication_state[1] == 0) {
+            self.genome.remove_pair(0);
+        }
+        debug_assert_eq!(
+            self.active_replicators() + self.unassigned_replicators,
+            self.num_replicators,
+            "replicators were created or lost"
+        );
+    }
+    /// Replicators held by replicons, one per replicated run. A replicon whose forks
+    /// have reached a chromosome end keeps its replicator until it merges.
+    pub fn active_replicators(&self) -> usize {
+        self.genome
+            .replication_state
+            .iter()
+            .step_by(2)
+            .filter(|&&length| length > 0)
+            .count()
+    }
+    /// Iterate over S-phase one step at a time, see [`StepIter`]
+    pub fn steps<'a>(&'a mut self, rng: &'a mut ChaCha8Rng) -> StepIter<'a> {
+        self.enter_s_phase();
+        StepIter::new(self, rng)
+    }
+    /// Run up to `steps` assign + replicate cycles, stopping early once fully replicated.
+    /// Equivalent to taking `steps` events from [`Cell::steps`] with the same RNG.
+    pub fn advance_by(
+        &mut self,
+        steps: usize,
+        rng: &mut ChaCha8Rng,
+    ) -> Result<AdvanceStats, ReplicationError> {
+        self.enter_s_phase();
+        let num_fired = self.firing_log.len();
+        let num_merged = self.termination_sites.len();
+        let mut steps_run = 0;
+        while steps_run < steps && !self.is_fully_replicated() {
+            self.check_converged()?;
+            self.step(rng)?;
+            steps_run += 1;
+        }
+        Ok(AdvanceStats {
+            steps: steps_run,
+            origins_fired: self.firing_log.len() - num_fired,
+            merges: self.termination_sites.len() - num_merged,
+        })
+    }
+    /// Leave G-phase without warmup. A cell already in S or G2 phase keeps its state.
+    fn enter_s_phase(&mut self) {
+        if self.cell_state == CellState::GPhase {
+            self.cell_state = CellState::SPhase;
+        }
+    }
+    pub(crate) fn step(&mut self, rng: &mut ChaCha8Rng) -> Result<(), ReplicationError> {
+        self.assign_replicators(rng)?;
+        self.utilization
+            .push(self.num_replicators - self.unassigned_replicators);
+        if !self.lesions.is_empty() {
+            self.repair_lesions(rng);
+        }
+        self.replicate_and_merge();
+        if !self.lesions.is_empty() {
+            self.record_rescues();
+        }
+        #[cfg(feature = "std")]
+        if let Some(track) = &mut self.timing_track {
+            track.record(&self.genome, self.iteration);
+        }
+        if let Some(curve) = &mut self.fraction_curve {
+            curve.push(self.genome.fraction_replicated());
+        }
+        if let Some((n_bins, map)) = &mut self.fork_density_map {
+            map.push(self.genome.binned_fork_count(*n_bins)?);
+        }
+        self.iteration += 1;
+        if self.is_fully_replicated() {
+            self.cell_state = CellState::G2Phase;
+        }
+        Ok(())
+    }
+    /// Fail runs that can no longer finish: without replicators, without a dNTP budget,
+    /// recording a fork density map without bins, or out of iterations
+    pub(crate) fn check_converged(&self) -> Result<(), ReplicationError> {
+        if self.num_replicators == 0 {
+            return Err(ReplicationError::NoReplicators);
+        }
+        if self.dntp_budget_per_step == Some(0) {
+            return Err(ReplicationError::InvalidConfig(
+                "dntp_budget_per_step must be positive".to_string(),
+            ));
+        }
+        if matches!(self.fork_density_map, Some((0, _))) {
+            return Err(ReplicationError::InvalidConfig(
+                "fork density map n_bins must be positive".to_string(),
+            ));
+        }
+        if self.iteration >= self.max_iterations {
+            return Err(ReplicationError::DidNotConverge {
+                iterations: self.iteration,
+            });
+        }
+        Ok(())
+    }
+    /// Run G-phase warmup if still in G-phase, then replicate to completion. A bare
+    /// `g_phase_prob` selects [`GPhaseModel::Stochastic`].
+    pub fn full_replication(
+        &mut self,
+        g_phase: impl Into<GPhaseModel>,
+        seed: u64,
+    ) -> Result<ReplicationSummary, ReplicationError> {
+        self.full_replication_streaming(g_phase, seed, usize::MAX, &mut |_| {})
+    }
+    /// [`Cell::full_replication`] that hands the genome to `sink` after every `every_n`
+    /// iterations, e.g. to render or serialize frames without keeping them in memory
+    pub fn full_replication_streaming(
+        &mut self,
+        g_phase: impl Into<GPhaseModel>,
+        seed: u64,
+        every_n: usize,
+        sink: &mut dyn FnMut(&Genome),
+    ) -> Result<ReplicationSummary, ReplicationError> {
+        if every_n == 0 {
+            return Err(ReplicationError::InvalidConfig(
+                "every_n must be positive".to_string(),
+            ));
+        }
+        let g_phase = g_phase.into();
+        g_phase.validate()?;
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+
+        let mut warmup_iterations: usize = 0;
+        if self.cell_state == CellState::GPhase {
+            warmup_iterations = g_phase.warmup(&mut rng);
+            self.cell_state = CellState::SPhase;
+        }
+
+        // Replication run
+        let rng_checkpoint = RngCheckpoint::capture(&rng);
+        let first_iteration = self.iteration;
+        while !self.is_fully_replicated() {
+            self.check_converged()?;
+            self.step(&mut rng)?;
+            if (self.iteration - first_iteration).is_multiple_of(every_n) {
+                sink(&self.genome);
+            }
+        }
+        Ok(self.summary_since(warmup_iterations, first_iteration, rng_checkpoint))
+    }
+    /// Replicate the genome `max_rounds` times without dividing, resetting it to
+    /// unreplicated between rounds and doubling [`Cell::ploidy`] at each reset. Rounds
+    /// after the first use their own RNG stream of `seed`. Each reset clears the firing
+    /// log, termination sites, rescues and passive origins, so the replicon readouts
+    /// describe the last round; the returned summaries cover every round.
+    pub fn endoreduplicate(
+        &mut self,
+        g_phase: impl Into<GPhaseModel>,
+        seed: u64,
+        max_rounds: usize,
+    ) -> Result<Vec<ReplicationSummary>, ReplicationError> {
+        let mut summaries = Vec::with_capacity(max_rounds);
+        if max_rounds == 0 {
+            return Ok(summaries);
+        }
+        summaries.push(self.full_replication(g_phase, seed)?);
+        for round in 1..max_rounds {
+            self.genome.reset();
+            self.cell_state = CellState::SPhase;
+            self.unassigned_replicators = self.num_replicators;
+            self.licensed_origins = None;
+            self.firing_log.clear();
+            self.termination_sites.clear();
+            self.rescue_events.clear();
+            self.passive_origins.clear();
+            #[cfg(feature = "std")]
+            if self.timing_track.is_some() {
+                self.timing_track = Some(
+                    TimingTrack::new(self.genome.genome_length)
+                        .with_coordinate_offset(self.genome.coordinate_offset()),
+                );
+            }
+            self.ploidy *= 2;
+            let mut rng = ChaCha8Rng::seed_from_u64(seed);
+            rng.set_stream(round as u64);
+            summaries.push(self.run_until_fraction(1.0, &mut rng)?);
+        }
+        Ok(summaries)
+    }
+    /// Split a fully replicated cell into two diploid daughters back in G1, each with an
+    /// unreplicated genome and fresh records but the parent's configuration. Give each
+    /// daughter its own seed when running it so their S-phases differ. Any speed jitter
+    /// is reseeded per daughter from the parent's jitter RNG, so daughter forks vary
+    /// independently.
+    pub fn divide(&self) -> Result<(Cell, Cell), ReplicationError> {
+        if !self.is_fully_replicated() {
+            return Err(ReplicationError::NotFullyReplicated);
+        }
+        let mut daughter = self.clone();
+        daughter.genome.reset();
+        daughter.cell_state = CellState::GPhase;
+        daughter.unassigned_replicators = daughter.num_replicators;
+        daughter.iteration = 0;
+        daughter.firing_log.clear();
+        daughter.termination_sites.clear();
+        daughter.rescue_events.clear();
+        daughter.fork_restarts = 0;
+        daughter.lagging_strand_bases = 0;
+        daughter.refire_rejections = 0;
+        daughter.utilization.clear();
+        daughter.licensed_origins = None;
+        daughter.passive_origins.clear();
+        daughter.ploidy = 2;
+        #[cfg(feature = "std")]
+        if let Some(track) = &mut daughter.timing_track {
+            *track = TimingTrack::new(daughter.genome.genome_length)
+                .with_coordinate_offset(track.coordinate_offset());
+        }
+        if let Some(curve) = &mut daughter.fraction_curve {
+            curve.clear();
+        }
+        if let Some((_, map)) = &mut daughter.fork_density_map {
+            map.clear();
+        }
+        let mut first = daughter.clone();
+        let mut second = daughter;
+        if let Some((_, parent_rng)) = &self.speed_jitter {
+            let mut parent_rng = parent_rng.clone();
+            let daughter_jitters = [&mut first, &mut second]
+                .into_iter()
+                .filter_map(|cell| cell.speed_jitter.as_mut());
+            for (jitter, rng) in daughter_jitters {
+                jitter.seed = parent_rng.gen();
+                *rng = ChaCha8Rng::seed_from_u64(jitter.seed);
+            }
+        }
+        Ok((first, second))
+    }
+    /// Genome copies the cell carried into its latest replication round, starting diploid
+    pub fn ploidy(&self) -> usize {
+        self.ploidy
+    }
+    /// Step S-phase until at least `target` of the genome is replicated, leaving the
+    /// rest of the run unfinished. `target` is clamped to (0, 1].
+    pub fn run_until_fraction(
+        &mut self,
+        target: f64,
+        rng: &mut ChaCha8Rng,
+    ) -> Result<ReplicationSummary, ReplicationError> {
+        let target = target.clamp(f64::MIN_POSITIVE, 1.0);
+        self.enter_s_phase();
+        let rng_checkpoint = RngCheckpoint::capture(rng);
+        let first_iteration = self.iteration;
+        while self.genome.fraction_replicated() < target && !self.is_fully_replicated() {
+            self.check_converged()?;
+            self.step(rng)?;
+        }
+        Ok(self.summary_since(0, first_iteration, rng_checkpoint))
+    }
+    /// Re-run the S-phase that produced `summary` to completion, restoring its RNG state
+    /// at entry. The cell must be in the state the original run started from, e.g. a
+    /// fresh cell built with the same configuration.
+    pub fn replay(
+        &mut self,
+        summary: &ReplicationSummary,
+    ) -> Result<ReplicationSummary, ReplicationError> {
+        let mut rng = summary.rng_checkpoint.restore();
+        self.cell_state = CellState::SPhase;
+        let first_iteration = self.iteration;
+        while !self.is_fully_replicated() {
+            self.check_converged()?;
+            self.step(&mut rng)?;
+        }
+        Ok(self.summary_since(
+            summary.warmup_iterations,
+            first_iteration,
+            summary.rng_checkpoint,
+        ))
+    }
+    fn summary_since(
+        &self,
+        warmup_iterations: usize,
+        first_iteration: usize,
+        rng_checkpoint: RngCheckpoint,
+    ) -> ReplicationSummary {
+        // Each step records one utilization entry, including steps before a restore
+        let num_iterations = self.iteration - first_iteration;
+        ReplicationSummary {
+            warmup_iterations,
+            num_iterations,
+            s_phase: first_iteration..self.iteration,
+            clock: self.clock(),
+            utilization: self.utilization[(self.utilization.len() - num_iterations)..].to_vec(),
+            rng_checkpoint,
+        }
+    }
+    /// Simulate two-fraction Repli-seq: the binned replicated profile once `early_cutoff`
+    /// of the genome is replicated, and the additional signal gained by finishing S-phase
+    pub fn repli_seq(
+        &mut self,
+        early_cutoff: f64,
+        n_bins: usize,
+        rng: &mut ChaCha8Rng,
+    ) -> Result<(Vec<f64>, Vec<f64>), ReplicationError> {
+        self.run_until_fraction(early_cutoff, rng)?;
+        let early = self.genome.binned_fraction_replicated(n_bins)?;
+        self.run_until_fraction(1.0, rng)?;
+        let late = self
+            .genome
+            .binned_fraction_replicated(n_bins)?
+            .iter()
+            .zip(&early)
+            .map(|(total, early_signal)| total - early_signal)
+            .collect();
+        Ok((early, late))
+    }
+}
+
+impl Default for Cell {
+    fn default() -> Self {
+        Cell::preset_human_chr1()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "std")]
+    use crate::stats::std;
+
+    #[test]
+    fn human_chr1_preset_converges() {
+        let config = CellConfig::preset_human_chr1();
+        let mut cell = Cell::default();
+        let summary = cell.full_replication(config.g_phase_prob, 1701).unwrap();
+        assert!(cell.is_fully_replicated());
+        assert!(summary.num_iterations > 0);
+    }
+
+    #[test]
+    fn lesion_delays_replication_beyond_it() {
+        let run = |lesions: Vec<Lesion>| {
+            let mut cell = Cell::new(20_000, 1, 50)
+                .with_firing_probability(1.0)
+                .with_firing_sampler(FixedSampler(5_000))
+                .with_lesions(lesions);
+            let mut rng = ChaCha8Rng::seed_from_u64(3);
+            let summary = cell.run_until_fraction(1.0, &mut rng).unwrap();
+            (cell, summary.num_iterations)
+        };
+        let (_, unhindered) = run(Vec::new());
+        let (repaired, delayed) = run(vec![Lesion {
+            position: 15_000,
+            repair_prob: 0.02,
+        }]);
+        assert!(delayed > unhindered);
+        assert!(repaired.lesions().is_empty());
+
+        let mut blocked = Cell::new(20_000, 1, 50)
+            .with_firing_probability(1.0)
+            .with_firing_sampler(FixedSampler(5_000))
+            .with_lesions(vec![Lesion {
+                position: 15_000,
+                repair_prob: 0.0,
+            }]);
+        let mut rng = ChaCha8Rng::seed_from_u64(3);
+        blocked.advance_by(1_000, &mut rng).unwrap();
+        assert_eq!(
+            blocked.genome().unreplicated_intervals(),
+            vec![15_000..20_000]
+        );
+        assert_eq!(blocked.fork_restarts(), 0);
+    }
+
+    #[test]
+    fn certain_fork_restart_leaves_no_gap() {
+        let mut cell = Cell::new(20_000, 1, 50)
+            .with_firing_probability(1.0)
+            .with_firing_sampler(FixedSampler(5_000))
+            .with_lesions(vec![Lesion {
+                position: 15_000,
+                repair_prob: 0.0,
+            }])
+            .with_fork_restart_prob(1.0);
+        let mut rng = ChaCha8Rng::seed_from_u64(3);
+        cell.run_until_fraction(1.0, &mut rng).unwrap();
+        assert!(cell.is_fully_replicated());
+        assert!(cell.lesions().is_empty());
+        assert_eq!(cell.fork_restarts(), 1);
+        assert!(cell.rescue_events().is_empty());
+    }
+
+    #[test]
+    fn licensed_origin_on_a_lesion_does_not_fire() {
+        let mut cell = Cell::new(20_000, 1, 50)
+            .with_firing_probability(1.0)
+            .with_firing_sampler(FixedSampler(5_000))
+            .with_licensing(LicensingMode::PreLicense)
+            .with_lesions(vec![Lesion {
+                position: 5_000,
+                repair_prob: 0.0,
+            }]);
+        let mut rng = ChaCha8Rng::seed_from_u64(3);
+        cell.advance_by(10, &mut rng).unwrap();
+        assert_eq!(cell.licensed_origins(), Some([5_000].as_slice()));
+        assert!(cell.firing_log().is_empty());
+    }
+
+    #[test]
+    fn inter_firing_times_are_geometric_at_constant_probability() {
+        let firing_probability = 2e-4;
+        let mut cell = Cell::new(10_000_000, 400, 1).with_firing_probability(firing_probability);
+        let mut rng = ChaCha8Rng::seed_from_u64(3);
+        cell.advance_by(1_000, &mut rng).unwrap();
+        // A step fires each further origin with the same chance of passing one of its
+        // sampling attempts, so the gaps are geometric on 0, 1, 2, ...
+        let success = 1.0 - (1.0 - firing_probability).powi(MAX_FIRING_ATTEMPTS as i32);
+        let expected_mean = (1.0 - success) / success;
+        let expected_variance = (1.0 - success) / success.powi(2);
+        assert!(cell.inter_firing_times().len() > 100);
+        assert!((cell.mean_inter_firing_time() / expected_mean - 1.0).abs() < 0.2);
+        assert!((cell.inter_firing_time_variance() / expected_variance - 1.0).abs() < 0.35);
+    }
+
+    /// Run with `cargo test --no-default-features` to exercise the `no_std` core
+    #[test]
+    #[cfg(not(feature = "std"))]
+    fn short_simulation_runs_without_std() {
+        let mut cell = Cell::from_config(&CellConfig::new(100_000, 10)).unwrap();
+        let summary = cell.full_replication(0.0, DEFAULT_SEED).unwrap();
+        assert!(cell.is_fully_replicated());
+        assert!(summary.num_iterations > 0);
+        assert_eq!(cell.termination_sites().len(), cell.firing_log().len() - 1);
+    }
+
+    #[test]
+    fn isolated_origin_travels_equally_both_ways() {
+        let mut cell = Cell::new(20_000, 1, 50)
+            .with_firing_probability(1.0)
+            .with_firing_sampler(FixedSampler(10_000));
+        cell.full_replication(0.5, 3).unwrap();
+        assert_eq!(cell.fork_travel_distances(), vec![(10_000, 10_000, 10_000)]);
+
+        // A partner fired 3 kb to the right cuts the facing forks short
+        let mut pair = Cell::new(20_000, 2, 50)
+            .with_firing_probability(1.0)
+            .with_firing_sampler(FixedSampler(5_000))
+            .with_origin_clusters(vec![vec![5_000, 8_000]]);
+        pair.full_replication(0.5, 3).unwrap();
+        let distances = pair.fork_travel_distances();
+        assert_eq!(distances.len(), 2);
+        let (left_origin, far_left, near_left) = distances[0];
+        let (right_origin, near_right, far_right) = distances[1];
+        assert_eq!((left_origin, far_left), (5_000, 5_000));
+        assert_eq!((right_origin, far_right), (8_000, 12_000));
+        assert_eq!(near_left + near_right, 3_000);
+        assert!(near_left.abs_diff(near_right) <= 100);
+        let sizes = pair.replicon_sizes();
+        assert_eq!(sizes, vec![far_left + near_left, near_right + far_right]);
+    }
+
+    #[test]
+    fn fork_travel_distances_after_endoreduplication() {
+        let mut cell = Cell::new(20_000, 4, 50);
+        cell.endoreduplicate(DEFAULT_G_PHASE_PROB, 3, 2).unwrap();
+        let distances = cell.fork_travel_distances();
+        assert_eq!(distances.len(), cell.replicon_count());
+        assert_eq!(
+            distances
+                .iter()
+                .map(|(_, left, right)| left + right)
+                .collect::<Vec<_>>(),
+            cell.replicon_sizes()
+        );
+    }
+
+    #[test]
+    fn fork_travel_distances_mid_run() {
+        let mut cell = Cell::new(30_000, 3, 50);
+        // Origin 5 kb is still growing; 15 kb and 20 kb have merged at 17.5 kb
+        cell.firing_log = vec![(0, 5_000), (0, 15_000), (0, 20_000)];
+        cell.termination_sites = vec![17_500];
+        cell.genome
+            .set_runs(vec![0, 4_000, 2_000, 7_000, 9_000, 8_000, 0, 0, 0]);
+        assert_eq!(
+            cell.fork_travel_distances(),
+            vec![
+                (5_000, 1_000, 1_000),
+                (15_000, 2_000, 2_500),
+                (20_000, 2_500, 2_000)
+            ]
+        );
+    }
+
+    #[test]
+    fn converging_fork_rescues_stalled_fork() {
+        let mut cell = Cell::new(20_000, 2, 50)
+            .with_firing_probability(1.0)
+            .with_firing_sampler(FixedSampler(5_000))
+            .with_origin_clusters(vec![vec![5_000, 15_000]])
+            .with_lesions(vec![Lesion {
+                position: 8_000,
+                repair_prob: 0.0,
+            }]);
+        let mut rng = ChaCha8Rng::seed_from_u64(3);
+        cell.run_until_fraction(1.0, &mut rng).unwrap();
+        assert!(cell.is_fully_replicated());
+        assert!(cell.lesions().is_empty());
+        assert_eq!(cell.rescue_events().len(), 1);
+        let (iteration, position) = cell.rescue_events()[0];
+        assert_eq!(position, 8_000);
+        // The leftward fork from 15 kb needs 7 kb at 50 bases per step to arrive
+        assert!(iteration + 1 >= 7_000 / 50);
+        assert_eq!(cell.termination_sites(), &[8_000]);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn daughters_start_in_g1_unreplicated() {
+        let mut parent = Cell::new(20_000, 4, 50).with_timing_track();
+        assert_eq!(
+            parent.divide().unwrap_err(),
+            ReplicationError::NotFullyReplicated
+        );
+        parent.full_replication(0.5, 3).unwrap();
+        let (first, second) = parent.divide().unwrap();
+        for daughter in [first, second] {
+            assert_eq!(daughter.cell_state, CellState::GPhase);
+            assert_eq!(daughter.genome().fraction_replicated(), 0.0);
+            assert_eq!(daughter.genome().unreplicated_intervals(), vec![0..20_000]);
+            assert!(daughter.firing_log().is_empty());
+            assert_eq!(daughter.iteration(), 0);
+            assert!(daughter.timing_track().is_some());
+        }
+    }
+
+    #[test]
+    fn daughters_of_an_endoreduplicated_cell_are_diploid_with_own_jitter() {
+        let mut parent = Cell::new(20_000, 4, 50).with_speed_jitter(SpeedJitter {
+            spread: 20,
+            seed: 1,
+        });
+        parent.endoreduplicate(DEFAULT_G_PHASE_PROB, 3, 2).unwrap();
+        assert_eq!(parent.ploidy(), 4);
+        let (mut first, mut second) = parent.divide().unwrap();
+        assert_eq!((first.ploidy(), second.ploidy()), (2, 2));
+        // The same firing seed leaves only the jitter to tell the daughters apart
+        for daughter in [&mut first, &mut second] {
+            let mut rng = ChaCha8Rng::seed_from_u64(5);
+            daughter.advance_by(20, &mut rng).unwrap();
+        }
+        assert_eq!(first.firing_log()[0], second.firing_log()[0]);
+        assert_ne!(
+            first.genome().replication_state(),
+            second.genome().replication_state()
+        );
+    }
+
+    #[test]
+    fn replicon_efficiency_matches_licensed_fraction_fired() {
+        let mut efficiencies = Vec::new();
+        for seed in 0..5 {
+            let mut cell = Cell::new(50_000, 10, 50).with_licensing(LicensingMode::PreLicense);
+            cell.full_replication(0.5, seed).unwrap();
+            // Every licensed origin ends up fired, passively replicated or still listed
+            let fired = cell.firing_log().len();
+            let licensed =
+                fired + cell.passive_origins().len() + cell.licensed_origins().unwrap().len();
+            let true_efficiency = fired as f64 / licensed as f64;
+            let estimate = cell.estimate_efficiency_from_replicons();
+            assert!((estimate - true_efficiency).abs() < 1e-9);
+            efficiencies.push(true_efficiency);
+        }
+        assert!(efficiencies.iter().any(|&efficiency| efficiency < 1.0));
+    }
+
+    #[test]
+    fn completed_run_enters_g2_phase() {
+        let mut cell = Cell::new(20_000, 4, 50);
+        let summary = cell.full_replication(0.5, 3).unwrap();
+        assert_eq!(cell.cell_state, CellState::G2Phase);
+        assert_eq!(summary.s_phase, 0..summary.num_iterations);
+
+        let mut partial = Cell::new(20_000, 4, 50);
+        let mut rng = ChaCha8Rng::seed_from_u64(3);
+        partial.run_until_fraction(0.5, &mut rng).unwrap();
+        assert_eq!(partial.cell_state, CellState::SPhase);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn checkpoint_delays_late_zones() {
+        let late_zone = 35_000..50_000;
+        let checkpoint = CheckpointConfig {
+            active_until_fraction: 0.6,
+            late_zones: vec![late_zone.clone()],
+            late_firing_factor: 0.05,
+        };
+        let mut unchecked_times = Vec::new();
+        let mut checked_times = Vec::new();
+        for seed in 0..5 {
+            let mut unchecked = Cell::new(50_000, 10, 50).with_timing_track();
+            let mut checked = Cell::new(50_000, 10, 50)
+                .with_timing_track()
+                .with_checkpoint(checkpoint.clone());
+            unchecked
+                .full_replication(DEFAULT_G_PHASE_PROB, seed)
+                .unwrap();
+            checked
+                .full_replication(DEFAULT_G_PHASE_PROB, seed)
+                .unwrap();
+            let mean_late_time = |cell: &Cell| {
+                cell.timing_track()
+                    .unwrap()
+                    .mean_time(late_zone.start, late_zone.end)
+            };
+            unchecked_times.push(mean_late_time(&unchecked));
+            checked_times.push(mean_late_time(&checked));
+        }
+        let unchecked_mean = unchecked_times.iter().sum::<f64>() / unchecked_times.len() as f64;
+        let checked_mean = checked_times.iter().sum::<f64>() / checked_times.len() as f64;
+        assert!(checked_mean > unchecked_mean);
+    }
+
+    #[test]
+    fn zero_density_regions_never_fire() {
+        let desert = 10_000..30_000;
+        for seed in 0..5 {
+            let mut cell = Cell::new(50_000, 10, 50)
+                .with_density_profile(vec![(desert.clone(), 0.0), (40_000..50_000, 2.0)]);
+            cell.full_replication(DEFAULT_G_PHASE_PROB, seed).unwrap();
+            assert!(cell.is_fully_replicated());
+            assert!(!cell.firing_log().is_empty());
+            assert!(cell
+                .firing_log()
+                .iter()
+                .all(|(_, position)| !desert.contains(position)));
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn heterochromatin_is_replicated_passively_and_last() {
+        let region = 20_000..30_000;
+        for seed in 0..5 {
+            let mut cell = Cell::new(50_000, 10, 50)
+                .with_timing_track()
+                .with_heterochromatin(vec![region.clone()]);
+            cell.full_replication(DEFAULT_G_PHASE_PROB, seed).unwrap();
+            assert!(cell.is_fully_replicated());
+            assert!(cell
+                .firing_log()
+                .iter()
+                .all(|(_, position)| !region.contains(position)));
+            let track = cell.timing_track().unwrap();
+            let heterochromatin_time = track.mean_time(region.start, region.end);
+            assert!(heterochromatin_time > track.mean_time(0, region.start));
+            assert!(heterochromatin_time > track.mean_time(region.end, 50_000));
+        }
+    }
+
+    #[test]
+    fn replicators_are_conserved_across_a_run() {
+        for seed in 0..5 {
+            let mut cell = Cell::new(20_000, 6, 50);
+            let mut rng = ChaCha8Rng::seed_from_u64(seed);
+            while !cell.is_fully_replicated() {
+                cell.step(&mut rng).unwrap();
+                assert_eq!(cell.active_replicators() + cell.unassigned_replicators, 6);
+            }
+        }
+        // An origin at the very start leaves its replicon touching the chromosome end
+        let mut cell = Cell::new(1_000, 2, 50)
+            .with_firing_probability(1.0)
+            .with_firing_sampler(FixedSampler(0));
+        let mut rng = ChaCha8Rng::seed_from_u64(3);
+        cell.advance_by(3, &mut rng).unwrap();
+        assert_eq!(cell.genome().replicated_intervals(), vec![0..151]);
+        assert_eq!(cell.active_replicators(), 1);
+        assert_eq!(cell.unassigned_replicators, 1);
+    }
+
+    #[test]
+    fn streaming_sink_sees_every_nth_iteration() {
+        let mut frames: Vec<f64> = Vec::new();
+        let mut cell = Cell::new(20_000, 4, 50);
+        let summary = cell
+            .full_replication_streaming(0.5, 3, 10, &mut |genome| {
+                frames.push(genome.fraction_replicated())
+            })
+            .unwrap();
+        assert_eq!(frames.len(), summary.num_iterations / 10);
+        assert!(frames.windows(2).all(|pair| pair[0] <= pair[1]));
+
+        let mut streamed = Cell::new(20_000, 4, 50);
+        let mut plain = Cell::new(20_000, 4, 50);
+        assert_eq!(
+            streamed.full_replication_streaming(0.5, 3, 1, &mut |_| {}),
+            plain.full_replication(0.5, 3)
+        );
+    }
+
+    #[test]
+    fn replayed_run_matches_original() {
+        let mut original = Cell::new(30_000, 6, 50);
+        let summary = original.full_replication(0.5, 21).unwrap();
+        let mut replayed = Cell::new(30_000, 6, 50);
+        let replay_summary = replayed.replay(&summary).unwrap();
+        assert_eq!(replay_summary, summary);
+        assert_eq!(
+            replayed.genome().replication_state(),
+            original.genome().replication_state()
+        );
+        assert_eq!(replayed.firing_log(), original.firing_log());
+    }
+
+    #[test]
+    fn origins_never_fire_in_runs_below_min_replicon_size() {
+        let continuous = (0..5).map(|seed| (LicensingMode::Continuous, seed));
+        let pre_licensed = (0..5).map(|seed| (LicensingMode::PreLicense, seed));
+        for (licensing, seed) in continuous.chain(pre_licensed) {
+            let mut cell = Cell::new(50_000, 10, 50)
+                .with_min_replicon_size(2_000)
+                .with_licensing(licensing)
+                .with_licensed_origin_count(100);
+            let mut rng = ChaCha8Rng::seed_from_u64(seed);
+            while !cell.is_fully_replicated() {
+                let before = cell.genome().unreplicated_intervals();
+                let num_fired = cell.firing_log().len();
+                cell.step(&mut rng).unwrap();
+                for (_, position) in &cell.firing_log()[num_fired..] {
+                    let run = before
+                        .iter()
+                        .find(|interval| interval.contains(position))
+                        .unwrap();
+                    assert!(run.len() >= 2_000);
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn terminal_margins_replicate_passively_and_last() {
+        for seed in 0..5 {
+            let mut cell = Cell::new(50_000, 10, 50)
+                .with_timing_track()
+                .with_terminal_exclusion(5_000);
+            cell.full_replication(DEFAULT_G_PHASE_PROB, seed).unwrap();
+            assert!(cell.is_fully_replicated());
+            assert!(cell
+                .firing_log()
+                .iter()
+                .all(|(_, position)| (5_000..45_000).contains(position)));
+            let track = cell.timing_track().unwrap();
+            let interior_time = track.mean_time(5_000, 45_000);
+            assert!(track.mean_time(0, 5_000) > interior_time);
+            assert!(track.mean_time(45_000, 50_000) > interior_time);
+        }
+    }
+
+    #[test]
+    fn leftover_replicators_wait_when_no_region_can_host() {
+        let mut cell = Cell::new(20, 10, 1)
+            .with_firing_probability(1.0)
+            .with_min_replicon_size(8);
+        let mut rng = ChaCha8Rng::seed_from_u64(3);
+        cell.assign_replicators(&mut rng).unwrap();
+        assert!(cell.unassigned_replicators > 0);
+        assert_eq!(cell.genome.hostable_region_count(), 0);
+        assert!(cell
+            .genome()
+            .unreplicated_intervals()
+            .iter()
+            .all(|interval| interval.len() < 8));
+    }
+
+    #[test]
+    fn adaptive_step_finishes_sooner_in_the_same_state() {
+        let mut fixed = Cell::new(200_000, 4, 50);
+        let mut adaptive = Cell::new(200_000, 4, 50).with_adaptive_step(AdaptiveStep {
+            fraction: 0.25,
+            max_step: 5_000,
+        });
+        let fixed_summary = fixed.full_replication(0.5, 6).unwrap();
+        let adaptive_summary = adaptive.full_replication(0.5, 6).unwrap();
+        assert!(adaptive_summary.num_iterations < fixed_summary.num_iterations);
+        assert_eq!(adaptive.genome().replicated_intervals(), vec![0..200_000]);
+        assert_eq!(
+            adaptive.genome().replicated_intervals(),
+            fixed.genome().replicated_intervals()
+        );
+    }
+
+    #[test]
+    fn replicons_tile_the_genome() {
+        let mut cell = Cell::new(50_000, 10, 50);
+        cell.full_replication(DEFAULT_G_PHASE_PROB, 11).unwrap();
+        let replicon_sizes = cell.replicon_sizes();
+        assert_eq!(replicon_sizes.len(), cell.firing_log().len());
+        assert_eq!(replicon_sizes.iter().sum::<usize>(), 50_000);
+        assert_eq!(
+            cell.inter_origin_distances().len(),
+            cell.firing_log().len() - 1
+        );
+        let total_binned: usize = cell
+            .replicon_size_histogram(1_000)
+            .unwrap()
+            .iter()
+            .map(|(_, count)| count)
+            .sum();
+        assert_eq!(total_binned, replicon_sizes.len());
+    }
+
+    #[test]
+    fn fixed_g_phase_has_exact_warmup() {
+        let mut cell = Cell::new(20_000, 4, 50);
+        let summary = cell.full_replication(GPhaseModel::Fixed(5), 2).unwrap();
+        assert_eq!(summary.warmup_iterations, 5);
+    }
+
+    #[test]
+    fn termination_density_bins_sites() {
+        let mut cell = Cell::new(100, 2, 50);
+        cell.termination_sites = vec![0, 24, 25, 50, 99];
+        assert_eq!(cell.termination_density(4).unwrap(), vec![2, 1, 1, 1]);
+        assert_eq!(cell.termination_density(1).unwrap(), vec![5]);
+        assert!(matches!(
+            cell.termination_density(0),
+            Err(ReplicationError::InvalidConfig(_))
+        ));
+    }
+
+    #[test]
+    fn repli_seq_early_signal_follows_early_origins() {
+        let mut cell = Cell::new(60_000, 10, 50).with_density_profile(vec![(30_000..60_000, 0.0)]);
+        let mut rng = ChaCha8Rng::seed_from_u64(5);
+        let (early, late) = cell.repli_seq(0.4, 6, &mut rng).unwrap();
+        assert!(cell.is_fully_replicated());
+        for (early_signal, late_signal) in early.iter().zip(&late) {
+            assert!((early_signal + late_signal - 1.0).abs() < 1e-12);
+        }
+        let origin_half: f64 = early[..3].iter().sum();
+        let desert_half: f64 = early[3..].iter().sum();
+        assert!(origin_half > desert_half);
+    }
+
+    #[test]
+    fn telomeres_remain_as_unreplicated_ends() {
+        for seed in 0..5 {
+            let mut cell = Cell::new(20_000, 5, 50).with_telomere_length(500);
+            cell.full_replication(DEFAULT_G_PHASE_PROB, seed).unwrap();
+            assert!(cell.is_fully_replicated());
+            assert_eq!(
+                cell.genome().unreplicated_intervals(),
+                vec![0..500, 19_500..20_000]
+            );
+            assert_eq!(cell.genome().fraction_replicated(), 0.95);
+        }
+    }
+
+    #[test]
+    fn okazaki_fragments_scale_with_genome_length() {
+        let mut short = Cell::new(20_000, 5, 50);
+        let mut long = Cell::new(40_000, 10, 50);
+        short.full_replication(DEFAULT_G_PHASE_PROB, 2).unwrap();
+        long.full_replication(DEFAULT_G_PHASE_PROB, 2).unwrap();
+        let ratio = long.okazaki_fragment_count() as f64 / short.okazaki_fragment_count() as f64;
+        assert!(short.okazaki_fragment_count() > 0);
+        assert!((ratio - 2.0).abs() < 0.05);
+
+        let mut fine = Cell::new(20_000, 5, 50).with_okazaki_fragment_size(
+            NonZeroUsize::new(DEFAULT_OKAZAKI_FRAGMENT_SIZE / 2).unwrap(),
+        );
+        fine.full_replication(DEFAULT_G_PHASE_PROB, 2).unwrap();
+        assert_eq!(
+            fine.okazaki_fragment_count(),
+            fine.lagging_strand_bases / (DEFAULT_OKAZAKI_FRAGMENT_SIZE / 2)
+        );
+    }
+
+    #[test]
+    fn cohesion_sites_attract_termination() {
+        let cohesion = CohesionSites {
+            positions: vec![25_000, 75_000],
+            window: 2_000,
+            factor: 0.9,
+        };
+        // Fraction of termination sites within the window of a cohesion site
+        let near_fraction = |enabled: bool| {
+            let (mut near, mut total) = (0, 0);
+            for seed in 0..20 {
+                let mut cell = Cell::new(100_000, 6, 50);
+                if enabled {
+                    cell = cell.with_cohesion_sites(cohesion.clone());
+                }
+                cell.full_replication(0.5, seed).unwrap();
+                total += cell.termination_sites().len();
+                near += cell
+                    .termination_sites()
+                    .iter()
+                    .filter(|site| {
+                        cohesion
+                            .positions
+                            .iter()
+                            .any(|position| position.abs_diff(**site) <= cohesion.window)
+                    })
+                    .count();
+            }
+            near as f64 / total as f64
+        };
+        assert!(near_fraction(true) > 2.0 * near_fraction(false));
+    }
+
+    #[test]
+    fn fork_suppression_lowers_firing_near_active_forks() {
+        let suppression = ForkSuppression {
+            window: 1_000,
+            factor: 0.5,
+        };
+        let mut cell = Cell::new(20_000, 2, 50)
+            .with_firing_probability(0.2)
+            .with_fork_suppression(suppression);
+        // One replicon over 4-6 kb, its forks at 3999 and 6000
+        cell.genome.set_runs(vec![0, 4_000, 2_000, 14_000, 0, 0, 0]);
+        let probability = |position| cell.firing_probability_at(0.2, position, false, true);
+        assert!((probability(3_500) - 0.1).abs() < 1e-12);
+        assert!((probability(6_500) - 0.1).abs() < 1e-12);
+        assert_eq!(probability(15_000), 0.2);
+
+        let mut unsuppressed = cell.clone();
+        unsuppressed.fork_suppression = None;
+        assert_eq!(
+            unsuppressed.firing_probability_at(0.2, 3_500, false, true),
+            0.2
+        );
+    }
+
+    #[test]
+    fn fork_slowdown_lengthens_s_phase() {
+        let slowdown = ForkSlowdown {
+            window: 2_000,
+            factor: 0.9,
+        };
+        for seed in 0..3 {
+            let mut normal = Cell::new(50_000, 10, 50);
+            let mut slowed = Cell::new(50_000, 10, 50).with_fork_slowdown(slowdown.clone());
+            let normal_summary = normal.full_replication(DEFAULT_G_PHASE_PROB, seed).unwrap();
+            let slowed_summary = slowed.full_replication(DEFAULT_G_PHASE_PROB, seed).unwrap();
+            assert!(slowed_summary.num_iterations > normal_summary.num_iterations);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn speed_jitter_widens_s_phase_spread() {
+        let durations = |spread: Option<usize>| -> Vec<usize> {
+            (0..20)
+                .map(|seed| {
+                    let mut cell = Cell::new(20_000, 2, 50);
+                    if let Some(spread) = spread {
+                        cell = cell.with_speed_jitter(SpeedJitter { spread, seed });
+                    }
+                    cell.full_replication(DEFAULT_G_PHASE_PROB, seed)
+                        .unwrap()
+                        .num_iterations
+                })
+                .collect()
+        };
+        assert!(std(&durations(Some(50))) > std(&durations(None)));
+    }
+
+    #[test]
+    fn zero_dntp_budget_is_rejected() {
+        let mut cell = Cell::new(50_000, 10, 50).with_dntp_budget(0);
+        assert!(matches!(
+            cell.full_replication(0.5, 3),
+            Err(ReplicationError::InvalidConfig(_))
+        ));
+    }
+
+    #[test]
+    fn dntp_budget_caps_movement_per_step() {
+        let mut unlimited = Cell::new(50_000, 10, 50);
+        let unlimited_summary = unlimited.full_replication(DEFAULT_G_PHASE_PROB, 4).unwrap();
+
+        let mut limited = Cell::new(50_000, 10, 50).with_dntp_budget(300);
+        let mut rng = ChaCha8Rng::seed_from_u64(4);
+        limited.cell_state = CellState::SPhase;
+        while !limited.is_fully_replicated() {
+            let before = limited.lagging_strand_bases;
+            limited.step(&mut rng).unwrap();
+            assert!(limited.lagging_strand_bases - before <= 300);
+        }
+        assert!(limited.iteration() > unlimited_summary.num_iterations);
+    }
+
+    #[test]
+    fn rising_schedule_fires_more_late_origins() {
+        let late_firings = |schedule: FiringSchedule| -> usize {
+            let mut cell = Cell::new(50_000, 20, 20).with_firing_schedule(schedule);
+            let summary = cell.full_replication(DEFAULT_G_PHASE_PROB, 9).unwrap();
+            cell.firing_log()
+                .iter()
+                .filter(|(iteration, _)| *iteration >= summary.num_iterations / 2)
+                .count()
+        };
+        let flat = late_firings(FiringSchedule {
+            start_prob: 0.0005,
+            end_prob: 0.0005,
+        });
+        let rising = late_firings(FiringSchedule {
+            start_prob: 0.0005,
+            end_prob: 0.05,
+        });
+        assert!(rising > flat);
+    }
+
+    #[test]
+    fn firing_schedule_leaves_the_configured_probability() {
+        let mut cell = Cell::new(50_000, 20, 20)
+            .with_firing_probability(0.3)
+            .with_firing_schedule(FiringSchedule {
+                start_prob: 0.0005,
+                end_prob: 0.05,
+            });
+        let mut rng = ChaCha8Rng::seed_from_u64(9);
+        cell.run_until_fraction(0.5, &mut rng).unwrap();
+        assert_eq!(cell.snapshot(&rng).firing_probability, 0.3);
+    }
+
+    #[test]
+    fn interference_spaces_out_origins() {
+        let mut cell = Cell::new(100_000, 10, 50).with_interference_distance(5_000);
+        cell.full_replication(DEFAULT_G_PHASE_PROB, 2).unwrap();
+        assert!(cell.inter_origin_distances().len() > 1);
+        assert!(cell
+            .inter_origin_distances()
+            .iter()
+            .all(|&distance| distance >= 5_000));
+    }
+
+    #[test]
+    fn interference_applies_to_licensed_origins() {
+        for seed in 0..5 {
+            let mut cell = Cell::new(100_000, 10, 50)
+                .with_licensing(LicensingMode::PreLicense)
+                .with_licensed_origin_count(60)
+                .with_interference_distance(5_000);
+            cell.full_replication(DEFAULT_G_PHASE_PROB, seed).unwrap();
+            assert!(cell.inter_origin_distances().len() > 1);
+            assert!(cell
+                .inter_origin_distances()
+                .iter()
+                .all(|&distance| distance >= 5_000));
+        }
+    }
+
+    #[test]
+    fn summary_reports_s_phase_in_minutes() {
+        let mut cell = Cell::new(20_000, 4, 50).with_minutes_per_iteration(0.5);
+        let summary = cell.full_replication(DEFAULT_G_PHASE_PROB, 1).unwrap();
+        assert_eq!(
+            summary.s_phase_minutes(),
+            summary.num_iterations as f64 * 0.5
+        );
+        assert_eq!(summary.clock.fork_speed_kb_per_min(), 0.1);
+    }
+
+    #[test]
+    fn run_until_fraction_stops_near_target() {
+        let (genome_length, num_replicators, replication_rate) = (100_000, 10, 50);
+        let mut cell = Cell::new(genome_length, num_replicators, replication_rate);
+        let mut rng = ChaCha8Rng::seed_from_u64(3);
+        let summary = cell.run_until_fraction(0.5, &mut rng).unwrap();
+        // One step fires at most every replicator and moves two forks each
+        let max_step = (num_replicators * (2 * replication_rate + 1)) as f64 / genome_length as f64;
+        let fraction = cell.genome().fraction_replicated();
+        assert!(summary.num_iterations > 0);
+        assert!(fraction >= 0.5);
+        assert!(fraction < 0.5 + max_step);
+        assert!(!cell.is_fully_replicated());
+    }
+
+    #[test]
+    fn run_until_fraction_respects_max_iterations() {
+        let mut cell = Cell::new(100_000, 1, 50).with_max_iterations(10);
+        let mut rng = ChaCha8Rng::seed_from_u64(3);
+        assert_eq!(
+            cell.run_until_fraction(2.0, &mut rng),
+            Err(ReplicationError::DidNotConverge { iterations: 10 })
+        );
+    }
+
+    #[test]
+    fn full_replication_reports_non_convergence() {
+        let mut cell = Cell::new(100_000, 1, 50).with_max_iterations(10);
+        assert_eq!(
+            cell.full_replication(DEFAULT_G_PHASE_PROB, 3),
+            Err(ReplicationError::DidNotConverge { iterations: 10 })
+        );
+    }
+
+    #[test]
+    fn assigning_to_replicated_genome_is_an_error() {
+        let mut cell = Cell::new(1_000, 2, 50);
+        cell.full_replication(DEFAULT_G_PHASE_PROB, 3).unwrap();
+        let mut rng = ChaCha8Rng::seed_from_u64(3);
+        assert_eq!(
+            cell.assign_replicators(&mut rng),
+            Err(ReplicationError::NoUnreplicatedRegions)
+        );
+    }
+
+    /// Proposes the same position every time
+    #[derive(Debug, Clone)]
+    struct FixedSampler(usize);
+
+    impl FiringSampler for FixedSampler {
+        fn sample(&mut self, _genome: &Genome, _rng: &mut ChaCha8Rng) -> Option<usize> {
+            Some(self.0)
+        }
+    }
+
+    #[test]
+    fn fork_density_spreads_out_from_origin() {
+        let mut cell = Cell::new(100_000, 1, 500)
+            .with_firing_probability(1.0)
+            .with_firing_sampler(FixedSampler(50_000))
+            .with_fork_density_map(10);
+        let mut rng = ChaCha8Rng::seed_from_u64(3);
+        cell.advance_by(30, &mut rng).unwrap();
+        let map = cell.fork_density_map().unwrap();
+        assert_eq!(map.len(), 30);
+        assert_eq!(map[0], vec![0, 0, 0, 0, 1, 1, 0, 0, 0, 0]);
+        assert_eq!(map[29], vec![0, 0, 0, 1, 0, 0, 1, 0, 0, 0]);
+    }
+
+    #[test]
+    fn fork_density_map_without_bins_is_rejected() {
+        let mut cell = Cell::new(100_000, 1, 500).with_fork_density_map(0);
+        let mut rng = ChaCha8Rng::seed_from_u64(3);
+        assert!(matches!(
+            cell.advance_by(1, &mut rng),
+            Err(ReplicationError::InvalidConfig(_))
+        ));
+    }
+
+    #[test]
+    fn cluster_partners_fire_in_the_same_iteration() {
+        let mut cell = Cell::new(10_000, 3, 50)
+            .with_firing_probability(1.0)
+            .with_firing_sampler(FixedSampler(1_000))
+            .with_origin_clusters(vec![vec![1_000, 5_000, 8_000]]);
+        let mut rng = ChaCha8Rng::seed_from_u64(3);
+        cell.assign_replicators(&mut rng).unwrap();
+        assert_eq!(cell.firing_log(), &[(0, 1_000), (0, 5_000), (0, 8_000)]);
+    }
+
+    #[test]
+    fn interfering_cluster_partners_do_not_fire() {
+        let mut cell = Cell::new(10_000, 3, 50)
+            .with_firing_probability(1.0)
+            .with_interference_distance(2_000)
+            .with_firing_sampler(FixedSampler(1_000))
+            .with_origin_clusters(vec![vec![1_000, 2_500, 8_000]]);
+        let mut rng = ChaCha8Rng::seed_from_u64(3);
+        cell.assign_replicators(&mut rng).unwrap();
+        assert_eq!(cell.firing_log(), &[(0, 1_000), (0, 8_000)]);
+    }
+
+    #[test]
+    fn replicated_positions_are_not_refired() {
+        let mut cell = Cell::new(1_000, 2, 50)
+            .with_firing_probability(1.0)
+            .with_firing_sampler(FixedSampler(0));
+        let mut rng = ChaCha8Rng::seed_from_u64(3);
+        cell.assign_replicators(&mut rng).unwrap();
+        assert_eq!(cell.firing_log(), &[(0, 0)]);
+        assert_eq!(cell.refire_rejections(), MAX_FIRING_ATTEMPTS);
+    }
+
+    #[test]
+    fn forks_stall_at_centromere_barrier() {
+        let mut cell = Cell::new(100, 1, 5)
+            .with_firing_probability(1.0)
+            .with_firing_sampler(FixedSampler(10))
+            .with_centromere(40, true);
+        let mut rng = ChaCha8Rng::seed_from_u64(3);
+        for _ in 0..20 {
+            cell.step(&mut rng).unwrap();
+        }
+        assert_eq!(cell.genome().replicated_intervals(), vec![0..40]);
+        assert_eq!(cell.genome().arm_fraction(), (1.0, 0.0));
+    }
+
+    #[test]
+    fn restored_snapshot_continues_uninterrupted_run() {
+        let mut uninterrupted = Cell::new(30_000, 6, 50);
+        let mut rng = ChaCha8Rng::seed_from_u64(8);
+        uninterrupted.run_until_fraction(1.0, &mut rng).unwrap();
+
+        let mut interrupted = Cell::new(30_000, 6, 50);
+        let mut rng = ChaCha8Rng::seed_from_u64(8);
+        interrupted.run_until_fraction(0.5, &mut rng).unwrap();
+        let fired_before = interrupted.firing_log().len();
+        let (mut restored, mut rng) = Cell::from_snapshot(interrupted.snapshot(&rng), 8);
+        let summary = restored.run_until_fraction(1.0, &mut rng).unwrap();
+
+        assert_eq!(summary.utilization.len(), summary.num_iterations);
+        assert_eq!(restored.iteration(), uninterrupted.iteration());
+        assert_eq!(
+            restored.firing_log(),
+            &uninterrupted.firing_log()[fired_before..]
+        );
+        assert!(restored.is_fully_replicated());
+    }
+
+    #[test]
+    fn utilization_is_bounded_by_replicator_pool() {
+        let mut cell = Cell::new(50_000, 10, 50);
+        let summary = cell.full_replication(DEFAULT_G_PHASE_PROB, 5).unwrap();
+        assert_eq!(summary.utilization.len(), summary.num_iterations);
+        assert!(summary.utilization.iter().all(|&active| active <= 10));
+        assert!(summary.mean_utilization() > 0.0);
+    }
+
+    #[test]
+    fn invalid_configs_are_rejected() {
+        let config = CellConfig {
+            replication_rate: 0,
+            ..CellConfig::default()
+        };
+        assert!(matches!(
+            Cell::from_config(&config),
+            Err(ReplicationError::InvalidConfig(_))
+        ));
+        let mut cell = Cell::new(1_000, 2, 50);
+        assert!(matches!(
+            cell.full_replication(1.0, 3),
+            Err(ReplicationError::InvalidConfig(_))
+        ));
+    }
+
+    #[test]
+    fn replicator_count_must_fit_genome() {
+        let config = CellConfig {
+            genome_length: 100,
+            num_replicators: 100,
+            ..CellConfig::default()
+        };
+        assert!(Cell::from_config(&config).is_ok());
+        let too_many = CellConfig {
+            num_replicators: 101,
+            ..config.clone()
+        };
+        assert_eq!(
+            too_many.validate(),
+            Err(ReplicationError::TooManyReplicators {
+                num_replicators: 101,
+                genome_length: 100
+            })
+        );
+        let none = CellConfig {
+            num_replicators: 0,
+            ..config
+        };
+        assert_eq!(none.validate(), Err(ReplicationError::NoReplicators));
+    }
+
+    #[test]
+    fn cell_without_replicators_fails_instead_of_hanging() {
+        let mut cell = Cell::new(1_000, 0, 50);
+        let mut rng = ChaCha8Rng::seed_from_u64(3);
+        assert_eq!(
+            cell.full_replication(0.5, 3),
+            Err(ReplicationError::NoReplicators)
+        );
+        assert_eq!(
+            cell.run_until_fraction(1.0, &mut rng),
+            Err(ReplicationError::NoReplicators)
+        );
+        assert_eq!(
+            cell.advance_by(10, &mut rng),
+            Err(ReplicationError::NoReplicators)
+        );
+        assert_eq!(cell.steps(&mut rng).count(), 0);
+        assert_eq!(
+            Cell::from_config(&CellConfig::new(1_000, 0)).err(),
+            Some(ReplicationError::NoReplicators)
+        );
+    }
+
+    #[test]
+    fn config_builder_round_trips() {
+        let built = CellConfig::new(30_000, 6)
+            .with_replication_rate(25)
+            .with_firing_probability(0.2)
+            .with_g_phase_prob(0.5)
+            .with_seed(9);
+        let literal = CellConfig {
+            genome_length: 30_000,
+            num_replicators: 6,
+            replication_rate: 25,
+            firing_probability: 0.2,
+            g_phase_prob: 0.5,
+            seed: 9,
+        };
+        assert_eq!(built, literal);
+        let mut from_config = Cell::from_config(&built).unwrap();
+        let mut from_new = Cell::new(30_000, 6, 25).with_firing_probability(0.2);
+        assert_eq!(
+            from_config.full_replication(built.g_phase_prob, built.seed),
+            from_new.full_replication(0.5, 9)
+        );
+        assert_eq!(from_config.firing_log(), from_new.firing_log());
+    }
+
+    #[test]
+    fn pre_licensed_origins_are_fixed_before_firing() {
+        let mut cell = Cell::new(50_000, 8, 50).with_licensing(LicensingMode::PreLicense);
+        let mut rng = ChaCha8Rng::seed_from_u64(4);
+        cell.step(&mut rng).unwrap();
+        let mut licensed: Vec<usize> = cell.licensed_origins().unwrap().to_vec();
+        licensed.extend(cell.firing_log().iter().map(|(_, position)| position));
+        assert_eq!(licensed.len(), 8);
+        while !cell.is_fully_replicated() {
+            cell.step(&mut rng).unwrap();
+        }
+        assert!(cell
+            .firing_log()
+            .iter()
+            .all(|(_, position)| licensed.contains(position)));
+        assert!(cell
+            .firing_log()
+            .iter()
+            .any(|(iteration, _)| *iteration > 0));
+    }
+
+    #[test]
+    fn only_licensed_origins_are_activated() {
+        let mut cell = Cell::new(50_000, 4, 50)
+            .with_licensing(LicensingMode::PreLicense)
+            .with_licensed_origin_count(30);
+        let mut rng = ChaCha8Rng::seed_from_u64(4);
+        cell.step(&mut rng).unwrap();
+        let mut licensed: Vec<usize> = cell.licensed_origins().unwrap().to_vec();
+        licensed.extend(cell.firing_log().iter().map(|(_, position)| position));
+        assert_eq!(licensed.len(), 30);
+        while !cell.is_fully_replicated() {
+            cell.step(&mut rng).unwrap();
+        }
+        assert!(cell.firing_log().len() > 4);
+        assert!(cell
+            .firing_log()
+            .iter()
+            .all(|(_, position)| licensed.contains(position)));
+    }
+
+    #[test]
+    fn every_replicon_boundary_is_one_merge() {
+        for seed in 0..5 {
+            let mut cell = Cell::new(20_000, 4, 50);
+            cell.full_replication(DEFAULT_G_PHASE_PROB, seed).unwrap();
+            assert_eq!(cell.total_merges(), cell.firing_log().len() - 1);
+        }
+    }
+
+    #[test]
+    fn endoreduplication_doubles_ploidy_each_round() {
+        let mut cell = Cell::new(20_000, 4, 50);
+        let summaries = cell.endoreduplicate(DEFAULT_G_PHASE_PROB, 3, 2).unwrap();
+        assert_eq!(summaries.len(), 2);
+        assert!(summaries.iter().all(|summary| summary.num_iterations > 0));
+        assert_eq!(cell.ploidy(), 4);
+        assert!(cell.is_fully_replicated());
+        // Replicon readouts cover the second round only
+        assert_eq!(cell.total_merges(), cell.firing_log().len() - 1);
+        assert_eq!(cell.replicon_count(), cell.firing_log().len());
+        assert_eq!(cell.replicon_sizes().len(), cell.replicon_count());
+        assert_eq!(cell.replicon_sizes().iter().sum::<usize>(), 20_000);
+        assert_eq!(cell.firing_table().len(), cell.replicon_count());
+        assert!(cell
+            .firing_log()
+            .iter()
+            .all(|(iteration, _)| *iteration >= summaries[0].num_iterations));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn endoreduplication_keeps_timing_coordinate_offset() {
+        let mut cell = Cell::new(20_000, 4, 50)
+            .with_timing_track()
+            .with_coordinate_offset(5_000);
+        cell.endoreduplicate(DEFAULT_G_PHASE_PROB, 3, 2).unwrap();
+        let track = cell.timing_track().unwrap();
+        assert_eq!(track.coordinate_offset(), 5_000);
+        let mut bedgraph: Vec<u8> = Vec::new();
+        track.write_bedgraph(&mut bedgraph, "chr1", 1_000).unwrap();
+        let bedgraph = String::from_utf8(bedgraph).unwrap();
+        assert!(bedgraph.starts_with("chr1\t5000\t6000\t"));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn early_domains_replicate_before_late_domains() {
+        let mut cell = Cell::new(100_000, 8, 50)
+            .with_domains(vec![
+                ReplicationDomain {
+                    range: 0..50_000,
+                    firing_multiplier: 2.0,
+                    timing: DomainTiming::Early,
+                },
+                ReplicationDomain {
+                    range: 50_000..100_000,
+                    firing_multiplier: 1.0,
+                    timing: DomainTiming::Late,
+                },
+            ])
+            .with_timing_track();
+        cell.full_replication(DEFAULT_G_PHASE_PROB, 7).unwrap();
+        let track = cell.timing_track().unwrap();
+        assert!(track.mean_time(0, 50_000) < track.mean_time(50_000, 100_000));
+    }
+
+    #[test]
+    fn passively_replicated_origins_are_not_replicons() {
+        /// Proposes two neighbouring positions in turn
+        #[derive(Debug, Clone)]
+        struct PairSampler(bool);
+        impl FiringSampler for PairSampler {
+            fn sample(&mut self, _genome: &Genome, _rng: &mut ChaCha8Rng) -> Option<usize> {
+                self.0 = !self.0;
+                Some(if self.0 { 500 } else { 510 })
+            }
+        }
+
+        let mut cell = Cell::new(1_000, 2, 50)
+            .with_firing_probability(1.0)
+            .with_density_profile(vec![(505..1_000, 0.0)])
+            .with_licensing(LicensingMode::PreLicense)
+            .with_firing_sampler(PairSampler(false));
+        cell.full_replication(DEFAULT_G_PHASE_PROB, 1).unwrap();
+        assert_eq!(cell.replicon_count(), 1);
+        assert_eq!(cell.licensed_origins(), Some(&[][..]));
+    }
+
+    #[test]
+    fn middle_origin_is_passively_replicated() {
+        /// Proposes three origins in turn
+        #[derive(Debug, Clone)]
+        struct TripleSampler(usize);
+        impl FiringSampler for TripleSampler {
+            fn sample(&mut self, _genome: &Genome, _rng: &mut ChaCha8Rng) -> Option<usize> {
+                self.0 += 1;
+                Some([300, 500, 700][self.0 % 3])
+            }
+        }
+
+        let mut cell = Cell::new(1_000, 3, 50)
+            .with_firing_probability(1.0)
+            .with_density_profile(vec![(450..550, 0.0)])
+            .with_licensing(LicensingMode::PreLicense)
+            .with_firing_sampler(TripleSampler(0));
+        cell.full_replication(DEFAULT_G_PHASE_PROB, 1).unwrap();
+        assert_eq!(cell.passive_origins(), &[500]);
+        assert_eq!(cell.replicon_count(), 2);
+    }
+
+    #[test]
+    fn merged_regions_are_removed_as_replication_runs() {
+        let mut cell = Cell::new(50_000, 5, 20);
+        let mut rng = ChaCha8Rng::seed_from_u64(6);
+        while !cell.is_fully_replicated() {
+            cell.step(&mut rng).unwrap();
+            let state = cell.genome().replication_state();
+            assert_eq!(state.len(), 2 * 5 + 3);
+            // Every unreplicated run between two occupied replicated runs is non-empty
+            let last_occupied = (0..state.len()).step_by(2).rfind(|&i| state[i] > 0);
+            for index in (1..last_occupied.unwrap_or(0)).step_by(2) {
+                assert!(state[index] > 0 || state[index - 1] == 0);
+            }
+        }
+        assert!(cell.firing_log().len() > 5);
+    }
+
+    #[test]
+    fn cached_cumsum_tracks_replication_state() {
+        for seed in 0..5 {
+            let mut cell = Cell::new(20_000, 8, 25);
+            let mut rng = ChaCha8Rng::seed_from_u64(seed);
+            while !cell.is_fully_replicated() {
+                cell.step(&mut rng).unwrap();
+                let fresh: Vec<usize> = cell
+                    .genome
+                    .replication_state
+                    .iter()
+                    .scan(0, |total, length| {
+                        *total += length;
+                        Some(*total)
+                    })
+                    .collect();
+                assert_eq!(cell.genome.cumsum, fresh);
+            }
+        }
+    }
+
+    #[test]
+    fn unreplicated_total_tracks_replication_state() {
+        for seed in 0..5 {
+            let mut cell = Cell::new(20_000, 8, 25).with_telomere_length(100);
+            let mut rng = ChaCha8Rng::seed_from_u64(seed);
+            while !cell.is_fully_replicated() {
+                cell.step(&mut rng).unwrap();
+                assert_eq!(
+                    cell.genome.unreplicated_total,
+                    cell.genome.scan_unreplicated_total()
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,159 @@
+use crate::cell::{Cell, CellConfig, GPhaseModel, ReplicationSummary};
+use crate::error::ReplicationError;
+#[cfg(feature = "std")]
+use crate::stats::mean;
+use alloc::vec::Vec;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+/// Two homologous copies of a chromosome sharing one configuration and one set of
+/// licensed origins. Each homolog is replicated by its own cell, so which licensed
+/// origins fire, and when, is independent between the copies.
+#[derive(Debug, Clone)]
+pub struct DiploidGenome {
+    homologs: [Cell; 2],
+    licensed_origins: Vec<usize>,
+}
+
+impl DiploidGenome {
+    /// Build both homologs from `config`, licensing one origin per replicator from
+    /// `config.seed` and handing the same set to each copy
+    pub fn from_config(config: &CellConfig) -> Result<Self, ReplicationError> {
+        let mut homolog = Cell::from_config(config)?;
+        let mut rng = ChaCha8Rng::seed_from_u64(config.seed);
+        let licensed_origins = homolog.license_origins(&mut rng);
+        let homolog = homolog.with_licensed_origins(licensed_origins.clone());
+        Ok(DiploidGenome {
+            homologs: [homolog.clone(), homolog],
+            licensed_origins,
+        })
+    }
+    /// Record per-base replication times on both homologs, needed for
+    /// [`DiploidGenome::allelic_asynchrony`]
+    #[cfg(feature = "std")]
+    pub fn with_timing_tracks(self) -> Self {
+        let [first, second] = self.homologs;
+        DiploidGenome {
+            homologs: [first.with_timing_track(), second.with_timing_track()],
+            licensed_origins: self.licensed_origins,
+        }
+    }
+    pub fn homologs(&self) -> &[Cell; 2] {
+        &self.homologs
+    }
+    /// Origins licensed on both homologs before S-phase
+    pub fn licensed_origins(&self) -> &[usize] {
+        &self.licensed_origins
+    }
+    pub fn is_fully_replicated(&self) -> bool {
+        self.homologs.iter().all(Cell::is_fully_replicated)
+    }
+    /// Mean absolute difference in replication iteration between homologous positions
+    /// replicated on both copies. NaN without timing tracks or shared replicated bases.
+    #[cfg(feature = "std")]
+    pub fn allelic_asynchrony(&self) -> f64 {
+        let [Some(first), Some(second)] = self.homologs.each_ref().map(Cell::timing_track) else {
+            return f64::NAN;
+        };
+        let differences: Vec<usize> = first
+            .times()
+            .iter()
+            .zip(second.times())
+            .filter_map(|(first, second)| Some(first.as_ref()?.abs_diff(*second.as_ref()?)))
+            .collect();
+        mean(&differences)
+    }
+    /// Replicate both homologs, each with its own RNG seeded from `seeds`
+    pub fn full_replication(
+        &mut self,
+        g_phase: impl Into<GPhaseModel>,
+        seeds: [u64; 2],
+    ) -> Result<[ReplicationSummary; 2], ReplicationError> {
+        let g_phase = g_phase.into();
+        let [first, second] = &mut self.homologs;
+        Ok([
+            first.full_replication(g_phase, seeds[0])?,
+            second.full_replication(g_phase, seeds[1])?,
+        ])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn small_config() -> CellConfig {
+        CellConfig {
+            genome_length: 50_000,
+            num_replicators: 5,
+            ..CellConfig::default()
+        }
+    }
+
+    #[test]
+    fn both_homologs_complete_with_independent_firing() {
+        let config = small_config();
+        let mut diploid = DiploidGenome::from_config(&config).unwrap();
+        assert!(!diploid.is_fully_replicated());
+        diploid
+            .full_replication(config.g_phase_prob, [1, 2])
+            .unwrap();
+        assert!(diploid.is_fully_replicated());
+        let [first, second] = diploid.homologs();
+        assert!(first.genome().is_fully_replicated());
+        assert!(second.genome().is_fully_replicated());
+        assert_ne!(first.firing_log(), second.firing_log());
+    }
+
+    #[test]
+    fn homologs_fire_from_the_shared_licensed_set() {
+        let config = small_config();
+        let mut diploid = DiploidGenome::from_config(&config).unwrap();
+        let licensed = diploid.licensed_origins().to_vec();
+        assert_eq!(licensed.len(), config.num_replicators);
+        for homolog in diploid.homologs() {
+            assert_eq!(homolog.licensed_origins(), Some(licensed.as_slice()));
+        }
+        diploid
+            .full_replication(config.g_phase_prob, [1, 2])
+            .unwrap();
+        for homolog in diploid.homologs() {
+            assert!(homolog
+                .firing_log()
+                .iter()
+                .all(|(_, position)| licensed.contains(position)));
+        }
+    }
+
+    #[test]
+    fn shared_seed_gives_identical_homologs() {
+        let config = small_config();
+        let mut diploid = DiploidGenome::from_config(&config).unwrap();
+        diploid
+            .full_replication(config.g_phase_prob, [7, 7])
+            .unwrap();
+        let [first, second] = diploid.homologs();
+        assert_eq!(first.firing_log(), second.firing_log());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn asynchrony_reflects_divergent_firing() {
+        let config = small_config();
+        let asynchrony = |seeds: [u64; 2]| {
+            let mut diploid = DiploidGenome::from_config(&config)
+                .unwrap()
+                .with_timing_tracks();
+            diploid
+                .full_replication(config.g_phase_prob, seeds)
+                .unwrap();
+            diploid.allelic_asynchrony()
+        };
+        assert_eq!(asynchrony([7, 7]), 0.0);
+        assert!(asynchrony([7, 8]) > 0.0);
+        assert!(DiploidGenome::from_config(&config)
+            .unwrap()
+            .allelic_asynchrony()
+            .is_nan());
+    }
+}
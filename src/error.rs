@@ -0,0 +1,70 @@
+use alloc::string::String;
+use core::error::Error;
+use core::fmt;
+
+/// Errors returned by the replication model
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReplicationError {
+    /// A position at or beyond the end of the genome was queried
+    OutOfBounds {
+        position: usize,
+        genome_length: usize,
+    },
+    /// The run hit its iteration limit before finishing
+    DidNotConverge { iterations: usize },
+    /// Origins were requested on a genome with nothing left to replicate
+    NoUnreplicatedRegions,
+    /// A parameter is outside the range the model can run with
+    InvalidConfig(String),
+    /// More replicators were requested than there are bases to fire from
+    TooManyReplicators {
+        num_replicators: usize,
+        genome_length: usize,
+    },
+    /// No replicators were requested, so S-phase could never start
+    NoReplicators,
+    /// An imported replication profile could not be read or does not tile the genome
+    InvalidProfile(String),
+    /// The cell tried to divide before its genome was fully replicated
+    NotFullyReplicated,
+}
+
+impl fmt::Display for ReplicationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReplicationError::OutOfBounds {
+                position,
+                genome_length,
+            } => write!(
+                f,
+                "Index {position} is too large, cannot index beyond genome length {genome_length}"
+            ),
+            ReplicationError::DidNotConverge { iterations } => {
+                write!(
+                    f,
+                    "Replication did not finish within {iterations} iterations"
+                )
+            }
+            ReplicationError::NoUnreplicatedRegions => {
+                write!(f, "No unreplicated regions left to assign replicators to")
+            }
+            ReplicationError::InvalidConfig(reason) => write!(f, "Invalid configuration: {reason}"),
+            ReplicationError::TooManyReplicators {
+                num_replicators,
+                genome_length,
+            } => write!(
+                f,
+                "{num_replicators} replicators cannot fit in a genome of length {genome_length}"
+            ),
+            ReplicationError::NoReplicators => write!(f, "num_replicators must be positive"),
+            ReplicationError::InvalidProfile(reason) => {
+                write!(f, "Invalid replication profile: {reason}")
+            }
+            ReplicationError::NotFullyReplicated => {
+                write!(f, "Cannot divide before the genome is fully replicated")
+            }
+        }
+    }
+}
+
+impl Error for ReplicationError {}
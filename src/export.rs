@@ -0,0 +1,422 @@
+use crate::cell::Cell;
+use crate::error::ReplicationError;
+use crate::genome::Genome;
+use crate::timing::TimingTrack;
+use std::io::{self, BufRead, BufReader, Read, Write};
+
+fn invalid_profile(reason: String) -> ReplicationError {
+    ReplicationError::InvalidProfile(reason)
+}
+
+/// Unit of the coordinates written by the exporters
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CoordinateUnit {
+    #[default]
+    Bp,
+    Kb,
+    Mb,
+}
+
+impl CoordinateUnit {
+    fn bases(self) -> usize {
+        match self {
+            CoordinateUnit::Bp => 1,
+            CoordinateUnit::Kb => 1_000,
+            CoordinateUnit::Mb => 1_000_000,
+        }
+    }
+}
+
+/// Number formatting for the exporters. The default writes whole bp coordinates and
+/// values in full, as the plain writers do.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ExportOptions {
+    pub units: CoordinateUnit,
+    /// Decimal places of coordinates and values, `None` for the shortest exact form
+    pub precision: Option<usize>,
+}
+
+impl ExportOptions {
+    /// Format a position in bp in these units and precision
+    pub fn coordinate(&self, position: usize) -> String {
+        match (self.units, self.precision) {
+            (CoordinateUnit::Bp, None) => position.to_string(),
+            (units, _) => self.value(position as f64 / units.bases() as f64),
+        }
+    }
+    fn value(&self, value: f64) -> String {
+        match self.precision {
+            Some(precision) => format!("{value:.precision$}"),
+            None => value.to_string(),
+        }
+    }
+}
+
+impl Genome {
+    /// Write every replicated and unreplicated run as a `start,end,replicated` row with
+    /// half-open coordinates, shifted by the coordinate offset
+    pub fn write_profile_csv<W: Write>(&self, writer: W) -> io::Result<()> {
+        self.write_profile_csv_with(writer, &ExportOptions::default())
+    }
+    /// [`Genome::write_profile_csv`] with coordinates formatted by `options`. Only bp
+    /// output can be read back by [`Genome::from_profile_csv`].
+    pub fn write_profile_csv_with<W: Write>(
+        &self,
+        mut writer: W,
+        options: &ExportOptions,
+    ) -> io::Result<()> {
+        let mut runs: Vec<(usize, usize, bool)> = self
+            .replicated_intervals()
+            .into_iter()
+            .map(|interval| (interval.start, interval.end, true))
+            .chain(
+                self.unreplicated_intervals()
+                    .into_iter()
+                    .map(|interval| (interval.start, interval.end, false)),
+            )
+            .collect();
+        runs.sort_unstable();
+        writeln!(writer, "start,end,replicated")?;
+        let offset = self.coordinate_offset();
+        for (start, end, replicated) in runs {
+            writeln!(
+                writer,
+                "{},{},{replicated}",
+                options.coordinate(start + offset),
+                options.coordinate(end + offset)
+            )?;
+        }
+        Ok(())
+    }
+    /// Rebuild a genome from the rows written by [`Genome::write_profile_csv`], padded
+    /// with room for `num_replicators` active origins like [`Genome::new`]. The rows must
+    /// tile `genome_length` bases in order; a nonzero first start becomes the coordinate
+    /// offset. Every replicated run holds an active origin, so a profile with more
+    /// replicated runs than `num_replicators` is an [`ReplicationError::InvalidConfig`].
+    pub fn from_profile_csv<R: Read>(
+        reader: R,
+        genome_length: usize,
+        num_replicators: usize,
+    ) -> Result<Genome, ReplicationError> {
+        // Runs alternate starting with a (possibly empty) replicated run
+        let mut runs: Vec<usize> = vec![0];
+        let mut first_start: Option<usize> = None;
+        let mut expected_start: usize = 0;
+        for (line_number, line) in BufReader::new(reader).lines().enumerate() {
+            let line = line.map_err(|error| invalid_profile(error.to_string()))?;
+            if line.trim().is_empty() || (line_number == 0 && line.starts_with("start")) {
+                continue;
+            }
+            let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+            let [start, end, replicated] = fields[..] else {
+                return Err(invalid_profile(format!(
+                    "line {} should have 3 fields: {line}",
+                    line_number + 1
+                )));
+            };
+            let parse_coordinate = |field: &str| {
+                field.parse::<usize>().map_err(|_| {
+                    invalid_profile(format!("line {}: bad coordinate {field}", line_number + 1))
+                })
+            };
+            let (start, end) = (parse_coordinate(start)?, parse_coordinate(end)?);
+            let offset = *first_start.get_or_insert(start);
+            let (Some(start), Some(end)) = (start.checked_sub(offset), end.checked_sub(offset))
+            else {
+                return Err(invalid_profile(format!(
+                    "line {}: run {start}..{end} starts before the first run at {offset}",
+                    line_number + 1
+                )));
+            };
+            let replicated = match replicated {
+                "true" | "1" => true,
+                "false" | "0" => false,
+                other => {
+                    return Err(invalid_profile(format!(
+                        "line {}: bad replicated flag {other}",
+                        line_number + 1
+                    )))
+                }
+            };
+            if start != expected_start || end < start {
+                return Err(invalid_profile(format!(
+                    "line {}: run {start}..{end} does not follow on from {expected_start}",
+                    line_number + 1
+                )));
+            }
+            if end == start {
+                continue;
+            }
+            let last_replicated = (runs.len() - 1).is_multiple_of(2);
+            match runs.last_mut() {
+                Some(last) if last_replicated == replicated => *last += end - start,
+                _ => runs.push(end - start),
+            }
+            expected_start = end;
+        }
+        if expected_start != genome_length {
+            return Err(invalid_profile(format!(
+                "runs cover {expected_start} bases of a {genome_length} base genome"
+            )));
+        }
+
+        let replicated_runs = runs.iter().step_by(2).filter(|&&length| length > 0).count();
+        if replicated_runs > num_replicators {
+            return Err(ReplicationError::InvalidConfig(format!(
+                "{replicated_runs} replicated runs need more than {num_replicators} replicators"
+            )));
+        }
+        // Runs alternate, so at most `2 * num_replicators + 1` are in use and the state
+        // keeps the odd length of `Genome::new`
+        runs.resize(num_replicators * 2 + 3, 0);
+        let mut genome = Genome::new(genome_length, num_replicators)
+            .with_coordinate_offset(first_start.unwrap_or(0));
+        genome.set_runs(runs);
+        Ok(genome)
+    }
+}
+
+impl Cell {
+    /// Write [`Cell::firing_table`] as `iteration,position,replicon_index` rows, with
+    /// positions shifted by the genome's coordinate offset
+    pub fn write_firing_csv<W: Write>(&self, writer: W) -> io::Result<()> {
+        self.write_firing_csv_with(writer, &ExportOptions::default())
+    }
+    /// [`Cell::write_firing_csv`] with positions formatted by `options`
+    pub fn write_firing_csv_with<W: Write>(
+        &self,
+        mut writer: W,
+        options: &ExportOptions,
+    ) -> io::Result<()> {
+        let offset = self.genome().coordinate_offset();
+        writeln!(writer, "iteration,position,replicon_index")?;
+        for event in self.firing_table() {
+            writeln!(
+                writer,
+                "{},{},{}",
+                event.iteration,
+                options.coordinate(event.position + offset),
+                event.replicon_index
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl TimingTrack {
+    /// Write the mean replication iteration of each `bin_size` bin as tab separated
+    /// `chrom start end value` bedGraph lines with 0-based half-open coordinates. Bins
+    /// with no replicated bases have a NaN value.
+    pub fn write_bedgraph<W: Write>(
+        &self,
+        writer: W,
+        chrom: &str,
+        bin_size: usize,
+    ) -> io::Result<()> {
+        self.write_bedgraph_with(writer, chrom, bin_size, &ExportOptions::default())
+    }
+    /// [`TimingTrack::write_bedgraph`] with values written to the precision in
+    /// `options`. Coordinates stay in bp, as the bedGraph format requires. A zero
+    /// `bin_size` fails with an [`io::ErrorKind::InvalidInput`] error wrapping
+    /// [`ReplicationError::InvalidConfig`].
+    pub fn write_bedgraph_with<W: Write>(
+        &self,
+        mut writer: W,
+        chrom: &str,
+        bin_size: usize,
+        options: &ExportOptions,
+    ) -> io::Result<()> {
+        if bin_size == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                ReplicationError::InvalidConfig("bin_size must be positive".to_string()),
+            ));
+        }
+        let genome_length = self.times().len();
+        let offset = self.coordinate_offset();
+        for start in (0..genome_length).step_by(bin_size) {
+            let end = (start + bin_size).min(genome_length);
+            writeln!(
+                writer,
+                "{chrom}\t{}\t{}\t{}",
+                start + offset,
+                end + offset,
+                options.value(self.mean_time(start, end))
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cell::Cell;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn profile_csv_round_trips() {
+        let mut cell = Cell::new(30_000, 6, 50);
+        let mut rng = ChaCha8Rng::seed_from_u64(4);
+        cell.run_until_fraction(0.5, &mut rng).unwrap();
+
+        let mut csv: Vec<u8> = Vec::new();
+        cell.genome().write_profile_csv(&mut csv).unwrap();
+        let imported = Genome::from_profile_csv(csv.as_slice(), 30_000, 6).unwrap();
+        assert_eq!(
+            imported.replication_state(),
+            cell.genome().replication_state()
+        );
+        assert_eq!(imported, *cell.genome());
+        assert_eq!(imported.replication_state().iter().sum::<usize>(), 30_000);
+    }
+
+    #[test]
+    fn profile_csv_reports_absolute_coordinates() {
+        let mut genome = Genome::new(100, 1).with_coordinate_offset(5_000);
+        genome.set_runs(vec![0, 40, 20, 40, 0]);
+        let mut csv: Vec<u8> = Vec::new();
+        genome.write_profile_csv(&mut csv).unwrap();
+        assert_eq!(
+            String::from_utf8(csv).unwrap(),
+            "start,end,replicated\n5000,5040,false\n5040,5060,true\n5060,5100,false\n"
+        );
+        assert_eq!(genome.is_replicated(50), Ok(true));
+
+        let mut csv: Vec<u8> = Vec::new();
+        genome.write_profile_csv(&mut csv).unwrap();
+        let imported = Genome::from_profile_csv(csv.as_slice(), 100, 1).unwrap();
+        assert_eq!(imported, genome);
+    }
+
+    #[test]
+    fn kb_export_scales_coordinates_to_requested_precision() {
+        let mut genome = Genome::new(100_000, 1).with_coordinate_offset(5_000);
+        genome.set_runs(vec![0, 40_250, 20_000, 39_750, 0]);
+        let options = ExportOptions {
+            units: CoordinateUnit::Kb,
+            precision: Some(3),
+        };
+        let mut csv: Vec<u8> = Vec::new();
+        genome.write_profile_csv_with(&mut csv, &options).unwrap();
+        assert_eq!(
+            String::from_utf8(csv).unwrap(),
+            "start,end,replicated\n\
+             5.000,45.250,false\n\
+             45.250,65.250,true\n\
+             65.250,105.000,false\n"
+        );
+        let mut default_csv: Vec<u8> = Vec::new();
+        genome
+            .write_profile_csv_with(&mut default_csv, &ExportOptions::default())
+            .unwrap();
+        let mut plain_csv: Vec<u8> = Vec::new();
+        genome.write_profile_csv(&mut plain_csv).unwrap();
+        assert_eq!(default_csv, plain_csv);
+    }
+
+    #[test]
+    fn firing_csv_has_one_row_per_replicon() {
+        let mut cell = Cell::new(30_000, 6, 50);
+        cell.full_replication(0.5, 8).unwrap();
+        let table = cell.firing_table();
+        assert_eq!(table.len(), cell.replicon_count());
+        let mut replicon_indices: Vec<usize> =
+            table.iter().map(|event| event.replicon_index).collect();
+        replicon_indices.sort_unstable();
+        assert_eq!(replicon_indices, (0..table.len()).collect::<Vec<_>>());
+
+        let mut csv: Vec<u8> = Vec::new();
+        cell.write_firing_csv(&mut csv).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        assert_eq!(csv.lines().count(), cell.replicon_count() + 1);
+        let first = table[0];
+        assert_eq!(
+            csv.lines().nth(1),
+            Some(
+                format!(
+                    "{},{},{}",
+                    first.iteration, first.position, first.replicon_index
+                )
+                .as_str()
+            )
+        );
+    }
+
+    #[test]
+    fn bedgraph_has_one_line_per_bin() {
+        let mut cell = Cell::new(10_050, 4, 50).with_timing_track();
+        cell.full_replication(0.5, 2).unwrap();
+        let mut bedgraph: Vec<u8> = Vec::new();
+        let track = cell.timing_track().unwrap();
+        track.write_bedgraph(&mut bedgraph, "chr1", 1_000).unwrap();
+
+        let lines: Vec<Vec<String>> = String::from_utf8(bedgraph)
+            .unwrap()
+            .lines()
+            .map(|line| line.split('\t').map(String::from).collect())
+            .collect();
+        assert_eq!(lines.len(), 10_050_usize.div_ceil(1_000));
+        let mut expected_start = 0;
+        for fields in &lines {
+            assert_eq!(fields[0], "chr1");
+            assert_eq!(fields[1].parse::<usize>().unwrap(), expected_start);
+            expected_start = fields[2].parse().unwrap();
+            assert!(fields[3].parse::<f64>().unwrap() >= 0.0);
+        }
+        assert_eq!(expected_start, 10_050);
+    }
+
+    #[test]
+    fn bedgraph_without_bin_size_is_rejected() {
+        let track = TimingTrack::new(100);
+        let error = track
+            .write_bedgraph(&mut Vec::new(), "chr1", 0)
+            .unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+        assert!(matches!(
+            error
+                .get_ref()
+                .and_then(|inner| inner.downcast_ref::<ReplicationError>()),
+            Some(ReplicationError::InvalidConfig(_))
+        ));
+    }
+
+    #[test]
+    fn profile_csv_rejects_gaps() {
+        let csv = "start,end,replicated\n0,10,true\n20,100,false\n";
+        assert!(matches!(
+            Genome::from_profile_csv(csv.as_bytes(), 100, 2),
+            Err(ReplicationError::InvalidProfile(_))
+        ));
+        let csv = "start,end,replicated\n0,10,true\n10,90,false\n";
+        assert!(matches!(
+            Genome::from_profile_csv(csv.as_bytes(), 100, 2),
+            Err(ReplicationError::InvalidProfile(_))
+        ));
+    }
+
+    #[test]
+    fn profile_csv_needs_a_replicator_per_replicated_run() {
+        let csv: String = (0..8)
+            .map(|run| format!("{},{},{}\n", run * 10, run * 10 + 10, run % 2 == 1))
+            .chain(["80,100,false\n".to_string()])
+            .collect();
+        assert!(matches!(
+            Genome::from_profile_csv(csv.as_bytes(), 100, 1),
+            Err(ReplicationError::InvalidConfig(_))
+        ));
+
+        // Four replicated runs fit four replicators, padded to the length of a new genome
+        let imported = Genome::from_profile_csv(csv.as_bytes(), 100, 4).unwrap();
+        assert_eq!(
+            imported.replication_state(),
+            &[0, 10, 10, 10, 10, 10, 10, 10, 10, 20, 0]
+        );
+        assert_eq!(
+            imported.replication_state().len(),
+            Genome::new(100, 4).replication_state().len()
+        );
+    }
+}
@@ -0,0 +1,776 @@
+use crate::error::ReplicationError;
+use alloc::format;
+use alloc::string::String;
+use alloc::string::ToString;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::Range;
+#[cfg(feature = "std")]
+use range_set_blaze::RangeSetBlaze;
+
+/// Characters in the track drawn by `Genome`'s `Display` impl
+const DISPLAY_WIDTH: usize = 80;
+
+/// Whether a replication domain fires early or late in S-phase
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DomainTiming {
+    /// Fires from the start of S-phase
+    Early,
+    /// Fires only once every early domain has been replicated
+    Late,
+}
+
+/// Megabase-scale unit of coordinated origin firing
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReplicationDomain {
+    pub range: Range<usize>,
+    /// Factor applied to the firing probability of origins in the domain
+    pub firing_multiplier: f64,
+    pub timing: DomainTiming,
+}
+
+/// Replication state of a single chromosome, stored as alternating replicated and
+/// unreplicated run lengths starting with a replicated run
+#[derive(Debug, Clone, PartialEq)]
+pub struct Genome {
+    pub(crate) genome_length: usize,
+    pub(crate) replication_state: Vec<usize>,
+    /// Running total of `replication_state`, i.e. the end coordinate of each run
+    pub(crate) cumsum: Vec<usize>,
+    /// Total length of the unreplicated runs, kept in step with `replication_state`
+    pub(crate) unreplicated_total: usize,
+    /// Relative origin density of regions, scaled so the densest region is 1.0
+    density_profile: Vec<(Range<usize>, f64)>,
+    /// Relative origin density of positions outside the profile's regions
+    background_density: f64,
+    /// Bases at each chromosome end that forks stop short of and origins never fire in
+    telomere_length: usize,
+    /// Position dividing the two chromosome arms
+    centromere: Option<usize>,
+    /// Whether forks stall at the centromere instead of passing through it
+    centromere_barrier: bool,
+    /// Non-overlapping replication domains, each origin belonging to at most one
+    domains: Vec<ReplicationDomain>,
+    /// Regions where no origins are licensed, replicated only by forks from outside
+    heterochromatin: Vec<Range<usize>>,
+    /// Shortest unreplicated run an origin may fire in
+    min_replicon_size: usize,
+    /// Bases at each chromosome end where origins never fire, replicated passively
+    terminal_exclusion: usize,
+    /// Position of local base 0 in the full genome, added to exported coordinates
+    coordinate_offset: usize,
+}
+
+impl Genome {
+    /// Unreplicated genome with room for `num_replicators` active origins
+    pub fn new(genome_length: usize, num_replicators: usize) -> Self {
+        let mut start_vec: Vec<usize> = vec![0; (num_replicators * 2) + 3];
+        start_vec[1] = genome_length;
+        let mut cumsum = vec![genome_length; start_vec.len()];
+        cumsum[0] = 0;
+        Genome {
+            genome_length,
+            replication_state: start_vec,
+            cumsum,
+            unreplicated_total: genome_length,
+            density_profile: Vec::new(),
+            background_density: 1.0,
+            telomere_length: 0,
+            centromere: None,
+            centromere_barrier: false,
+            domains: Vec::new(),
+            heterochromatin: Vec::new(),
+            min_replicon_size: 0,
+            terminal_exclusion: 0,
+            coordinate_offset: 0,
+        }
+    }
+    /// Leave `telomere_length` bases at each end unreplicated, modelling the end
+    /// replication problem. These bases do not count against full replication.
+    pub fn with_telomere_length(mut self, telomere_length: usize) -> Self {
+        self.telomere_length = telomere_length;
+        self
+    }
+    pub fn telomere_length(&self) -> usize {
+        self.telomere_length
+    }
+    /// Forbid origins within `terminal_exclusion` bases of either end. Unlike telomeres
+    /// these margins are still replicated, passively by forks from the interior.
+    pub fn with_terminal_exclusion(mut self, terminal_exclusion: usize) -> Self {
+        self.terminal_exclusion = terminal_exclusion;
+        self
+    }
+    pub fn terminal_exclusion(&self) -> usize {
+        self.terminal_exclusion
+    }
+    /// Place this genome at `coordinate_offset` within a larger one, e.g. to simulate a
+    /// single region. Exports report absolute coordinates while queries such as
+    /// [`Genome::is_replicated`] keep taking 0-based local positions.
+    pub fn with_coordinate_offset(mut self, coordinate_offset: usize) -> Self {
+        self.coordinate_offset = coordinate_offset;
+        self
+    }
+    pub fn coordinate_offset(&self) -> usize {
+        self.coordinate_offset
+    }
+    /// Split the chromosome into arms at `centromere`. With `barrier` set, forks stop at
+    /// the centromere so each arm is replicated by its own origins.
+    pub fn with_centromere(mut self, centromere: usize, barrier: bool) -> Self {
+        self.centromere = Some(centromere);
+        self.centromere_barrier = barrier;
+        self
+    }
+    pub fn centromere(&self) -> Option<usize> {
+        self.centromere
+    }
+    /// Replicated fraction of the left and right arms. Without a centromere both values
+    /// are the whole-genome fraction.
+    pub fn arm_fraction(&self) -> (f64, f64) {
+        match self.centromere {
+            Some(centromere) => (
+                self.fraction_replicated_in(0, centromere),
+                self.fraction_replicated_in(centromere, self.genome_length),
+            ),
+            None => (self.fraction_replicated(), self.fraction_replicated()),
+        }
+    }
+    /// Partition the genome into replication domains. Positions outside every domain fire
+    /// as an early domain with multiplier 1.0.
+    pub fn with_domains(mut self, domains: Vec<ReplicationDomain>) -> Self {
+        self.domains = domains;
+        self
+    }
+    pub fn domains(&self) -> &[ReplicationDomain] {
+        &self.domains
+    }
+    /// The domain containing `position`, if any
+    pub fn domain_at(&self, position: usize) -> Option<&ReplicationDomain> {
+        self.domains
+            .iter()
+            .find(|domain| domain.range.contains(&position))
+    }
+    /// Whether every early domain is fully replicated, opening late domains for firing
+    pub(crate) fn early_domains_replicated(&self) -> bool {
+        self.domains
+            .iter()
+            .filter(|domain| domain.timing == DomainTiming::Early)
+            .all(|domain| self.fraction_replicated_in(domain.range.start, domain.range.end) == 1.0)
+    }
+    /// Weight origin licensing by region. Each `(range, weight)` sets a non-negative
+    /// relative density, with positions outside every range at density 1.0.
+    pub fn with_density_profile(mut self, density_profile: Vec<(Range<usize>, f64)>) -> Self {
+        let max_weight = density_profile
+            .iter()
+            .map(|(_, weight)| *weight)
+            .fold(1.0, f64::max);
+        self.density_profile = density_profile
+            .into_iter()
+            .map(|(region, weight)| (region, weight.max(0.0) / max_weight))
+            .collect();
+        self.background_density = 1.0 / max_weight;
+        self
+    }
+    /// Mark constitutive heterochromatin, forbidding origin licensing in each region so
+    /// it is only replicated passively by forks entering from outside
+    pub fn with_heterochromatin(mut self, heterochromatin: Vec<Range<usize>>) -> Self {
+        self.heterochromatin = heterochromatin;
+        self
+    }
+    pub fn heterochromatin(&self) -> &[Range<usize>] {
+        &self.heterochromatin
+    }
+    /// Leave unreplicated runs shorter than `min_replicon_size` to passive replication,
+    /// modelling the limited resolution of origin licensing
+    pub fn with_min_replicon_size(mut self, min_replicon_size: usize) -> Self {
+        self.min_replicon_size = min_replicon_size;
+        self
+    }
+    pub fn min_replicon_size(&self) -> usize {
+        self.min_replicon_size
+    }
+    /// Whether an unreplicated run of `length` bases is long enough to host an origin
+    pub(crate) fn can_host_origin(&self, length: usize) -> bool {
+        length > 0 && length >= self.min_replicon_size
+    }
+    /// Unreplicated runs long enough to host an origin
+    pub(crate) fn hostable_region_count(&self) -> usize {
+        self.replication_state
+            .iter()
+            .skip(1)
+            .step_by(2)
+            .filter(|&&length| self.can_host_origin(length))
+            .count()
+    }
+    /// Whether `position` lies in an unreplicated run too short to host an origin
+    pub(crate) fn in_too_small_region(&self, position: usize) -> bool {
+        self.min_replicon_size > 0
+            && self.replication_state[self.run_index(position)] < self.min_replicon_size
+    }
+    /// Relative origin density at `position`, between 0.0 and 1.0
+    pub fn origin_density_at(&self, position: usize) -> f64 {
+        let margin = self.telomere_length.max(self.terminal_exclusion);
+        if position < margin
+            || position >= self.genome_length.saturating_sub(margin)
+            || self
+                .heterochromatin
+                .iter()
+                .any(|region| region.contains(&position))
+        {
+            return 0.0;
+        }
+        self.density_profile
+            .iter()
+            .find(|(region, _)| region.contains(&position))
+            .map_or(self.background_density, |(_, weight)| *weight)
+    }
+    pub fn genome_length(&self) -> usize {
+        self.genome_length
+    }
+    pub fn replication_state(&self) -> &[usize] {
+        &self.replication_state
+    }
+    /// Fraction of the genome's bases that have been replicated
+    pub fn fraction_replicated(&self) -> f64 {
+        let replicated: usize = self.replication_state.iter().step_by(2).sum();
+        replicated as f64 / self.genome_length as f64
+    }
+    /// Replicated runs as half-open ranges of genome coordinates, adjacent runs joined
+    pub fn replicated_intervals(&self) -> Vec<Range<usize>> {
+        self.intervals_at_parity(0)
+    }
+    /// Unreplicated runs as half-open ranges of genome coordinates
+    pub fn unreplicated_intervals(&self) -> Vec<Range<usize>> {
+        self.intervals_at_parity(1)
+    }
+    fn intervals_at_parity(&self, parity: usize) -> Vec<Range<usize>> {
+        let mut intervals: Vec<Range<usize>> = Vec::new();
+        let mut start: usize = 0;
+        for (ind, length) in self.replication_state.iter().enumerate() {
+            let end = start + length;
+            if ind % 2 == parity && *length > 0 {
+                match intervals.last_mut() {
+                    Some(last) if last.end == start => last.end = end,
+                    _ => intervals.push(start..end),
+                }
+            }
+            start = end;
+        }
+        intervals
+    }
+    /// Half-open `(start, end)` ranges where exactly one of the two genomes is
+    /// replicated, empty when their replicated intervals agree
+    #[cfg(feature = "std")]
+    pub fn diff(&self, other: &Genome) -> Vec<(usize, usize)> {
+        let as_set = |genome: &Genome| -> RangeSetBlaze<usize> {
+            genome
+                .replicated_intervals()
+                .into_iter()
+                .map(|interval| interval.start..=interval.end - 1)
+                .collect()
+        };
+        (as_set(self) ^ as_set(other))
+            .ranges()
+            .map(|range| (*range.start(), range.end() + 1))
+            .collect()
+    }
+    /// Replicated fraction of the window `start..end`, clamped to the genome. Empty
+    /// windows give 0.0.
+    pub fn fraction_replicated_in(&self, start: usize, end: usize) -> f64 {
+        let end = end.min(self.genome_length);
+        if start >= end {
+            return 0.0;
+        }
+        let replicated: usize = self
+            .replicated_intervals()
+            .iter()
+            .map(|interval| {
+                interval
+                    .end
+                    .min(end)
+                    .saturating_sub(interval.start.max(start))
+            })
+            .sum();
+        replicated as f64 / (end - start) as f64
+    }
+    /// Replicated fraction of each of `n_bins` equal-width windows across the genome
+    pub fn binned_fraction_replicated(&self, n_bins: usize) -> Result<Vec<f64>, ReplicationError> {
+        if n_bins == 0 {
+            return Err(ReplicationError::InvalidConfig(
+                "n_bins must be positive".to_string(),
+            ));
+        }
+        let bounds: Vec<usize> = (0..=n_bins)
+            .map(|bin| bin * self.genome_length / n_bins)
+            .collect();
+        let mut replicated = vec![0; n_bins];
+        for interval in self.replicated_intervals() {
+            let mut bin = bounds.partition_point(|&bound| bound <= interval.start) - 1;
+            while bin < n_bins && bounds[bin] < interval.end {
+                let overlap_start = interval.start.max(bounds[bin]);
+                let overlap_end = interval.end.min(bounds[bin + 1]);
+                replicated[bin] += overlap_end.saturating_sub(overlap_start);
+                bin += 1;
+            }
+        }
+        Ok(replicated
+            .iter()
+            .enumerate()
+            .map(|(bin, count)| *count as f64 / (bounds[bin + 1] - bounds[bin]) as f64)
+            .collect())
+    }
+    /// Whether the base at `position` has been replicated
+    pub fn is_replicated(&self, position: usize) -> Result<bool, ReplicationError> {
+        // Handle out of bounds
+        if position >= self.genome_length {
+            return Err(ReplicationError::OutOfBounds {
+                position,
+                genome_length: self.genome_length,
+            });
+        }
+        // All even indexes are replicated ranges
+        Ok(self.run_index(position).is_multiple_of(2))
+    }
+    /// Replicated status of each position in a single sweep over the runs, in the order
+    /// given. Positions at or beyond the genome end are reported as unreplicated.
+    pub fn replicated_status_batch(&self, positions: &[usize]) -> Vec<bool> {
+        let mut order: Vec<usize> = (0..positions.len()).collect();
+        order.sort_unstable_by_key(|&query| positions[query]);
+        let mut status = vec![false; positions.len()];
+        let mut run_index = 0;
+        for query in order {
+            let position = positions[query];
+            if position >= self.genome_length {
+                continue;
+            }
+            while self.cumsum[run_index] <= position {
+                run_index += 1;
+            }
+            status[query] = run_index.is_multiple_of(2);
+        }
+        status
+    }
+    /// Number of forks, i.e. boundaries where an occupied replicated run meets a
+    /// non-empty unreplicated run
+    pub fn active_fork_count(&self) -> usize {
+        let state = &self.replication_state;
+        (1..state.len() - 1)
+            .step_by(2)
+            .filter(|&index| state[index] > 0)
+            .map(|index| usize::from(state[index - 1] > 0) + usize::from(state[index + 1] > 0))
+            .sum()
+    }
+    /// Forks counted in each of `n_bins` equal bins, placing each fork at the first
+    /// unreplicated base it will replicate next
+    pub fn binned_fork_count(&self, n_bins: usize) -> Result<Vec<usize>, ReplicationError> {
+        if n_bins == 0 {
+            return Err(ReplicationError::InvalidConfig(
+                "n_bins must be positive".to_string(),
+            ));
+        }
+        let mut counts = vec![0; n_bins];
+        for position in self.fork_positions() {
+            counts[position * n_bins / self.genome_length] += 1;
+        }
+        Ok(counts)
+    }
+    /// Position of each active fork, the first unreplicated base it will replicate next,
+    /// from the genome start
+    pub fn fork_positions(&self) -> Vec<usize> {
+        let state = &self.replication_state;
+        let mut positions = Vec::new();
+        for index in (1..state.len() - 1).step_by(2) {
+            if state[index] == 0 {
+                continue;
+            }
+            if state[index - 1] > 0 {
+                positions.push(self.cumsum[index] - state[index]);
+            }
+            if state[index + 1] > 0 {
+                positions.push(self.cumsum[index] - 1);
+            }
+        }
+        positions
+    }
+    pub fn is_fully_replicated(&self) -> bool {
+        debug_assert_eq!(self.unreplicated_total, self.scan_unreplicated_total());
+        if self.unreplicated_total == 0 {
+            return true;
+        }
+        if self.unreplicated_total > 2 * self.telomere_length {
+            return false;
+        }
+        // genome is fully replicated if there's no positions in unreplicated (odd) storage
+        // indexes, other than those left in the telomeres
+        let core_end = self.genome_length.saturating_sub(self.telomere_length);
+        for (ind, val) in self.replication_state.iter().enumerate() {
+            if (ind % 2 != 0) & (*val != 0) {
+                let run_end = self.cumsum[ind];
+                if run_end > self.telomere_length && run_end - val < core_end {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+    /// Sum of the unreplicated runs, recomputed from the state
+    pub(crate) fn scan_unreplicated_total(&self) -> usize {
+        self.replication_state.iter().skip(1).step_by(2).sum()
+    }
+    /// Replace the state with `runs`, rebuilding the cached run ends and totals
+    pub(crate) fn set_runs(&mut self, runs: Vec<usize>) {
+        self.cumsum = runs
+            .iter()
+            .scan(0, |total, length| {
+                *total += length;
+                Some(*total)
+            })
+            .collect();
+        self.replication_state = runs;
+        self.unreplicated_total = self.scan_unreplicated_total();
+    }
+    /// Replace the state with hand-built or imported `runs`, alternating replicated and
+    /// unreplicated lengths from a replicated run. The runs are not checked, so pass the
+    /// genome through [`Genome::finalize`] before simulating on it.
+    pub fn with_replication_state(mut self, runs: Vec<usize>) -> Self {
+        self.set_runs(runs);
+        self
+    }
+    /// Validate an externally built state and make it safe to simulate on: the runs must
+    /// tile the genome, empty interior runs are folded away and trailing zeros are added
+    /// until `num_origins` more origins can fire
+    pub fn finalize(mut self, num_origins: usize) -> Result<Genome, ReplicationError> {
+        let covered: usize = self.replication_state.iter().sum();
+        if covered != self.genome_length {
+            return Err(ReplicationError::InvalidConfig(format!(
+                "runs cover {covered} bases of a {} base genome",
+                self.genome_length
+            )));
+        }
+        let mut runs = self.minimal_runs();
+        // Each origin splits an unreplicated run in two, adding two entries, and the
+        // state keeps an odd length ending in padding
+        let capacity = (runs.len() + 2 * num_origins + 1).max(self.replication_state.len()) | 1;
+        runs.resize(capacity, 0);
+        self.set_runs(runs);
+        Ok(self)
+    }
+    /// Rewrite the state in its minimal form, folding empty interior runs into their
+    /// neighbours. The vector keeps its length so there is still room for new origins.
+    pub fn compact(&mut self) {
+        let num_entries = self.replication_state.len();
+        let mut runs = self.minimal_runs();
+        runs.resize(num_entries, 0);
+        let mut total = 0;
+        for (index, &length) in runs.iter().enumerate() {
+            total += length;
+            self.cumsum[index] = total;
+        }
+        self.replication_state = runs;
+    }
+    /// Runs of the state with empty runs folded away, starting with a replicated run
+    fn minimal_runs(&self) -> Vec<usize> {
+        let mut runs: Vec<usize> = vec![0];
+        for (index, &length) in self.replication_state.iter().enumerate() {
+            if length == 0 {
+                continue;
+            }
+            if (runs.len() - 1) % 2 == index % 2 {
+                *runs.last_mut().unwrap() += length;
+            } else {
+                runs.push(length);
+            }
+        }
+        runs
+    }
+    /// Mark the whole genome unreplicated again, keeping its configuration
+    pub(crate) fn reset(&mut self) {
+        let num_entries = self.replication_state.len();
+        let fresh = Genome::new(self.genome_length, (num_entries - 3) / 2);
+        self.replication_state = fresh.replication_state;
+        self.cumsum = fresh.cumsum;
+        self.unreplicated_total = fresh.unreplicated_total;
+    }
+    /// Index of the run holding `position`, the first run ending after it
+    pub(crate) fn run_index(&self, position: usize) -> usize {
+        self.cumsum.partition_point(|&end| end <= position)
+    }
+    /// Bases of the unreplicated run at `index` that forks must leave as telomere
+    pub(crate) fn telomere_reserve(&self, index: usize) -> usize {
+        let run_end = self.cumsum[index];
+        let touches_start = run_end == self.replication_state[index];
+        let touches_end = run_end == self.genome_length;
+        if touches_start || touches_end {
+            self.telomere_length
+        } else {
+            0
+        }
+    }
+    /// Most bases a fork at `fork` can replicate before reaching a centromere barrier,
+    /// moving right if `rightward` and left otherwise
+    pub(crate) fn barrier_limit(&self, fork: usize, rightward: bool) -> usize {
+        match self.centromere {
+            Some(centromere) if self.centromere_barrier => {
+                if rightward && fork <= centromere {
+                    centromere - fork
+                } else if !rightward && fork >= centromere {
+                    fork - centromere
+                } else {
+                    usize::MAX
+                }
+            }
+            _ => usize::MAX,
+        }
+    }
+    /// Split the unreplicated run at `index` around a single replicated base at `position`
+    pub(crate) fn insert_origin(&mut self, index: usize, position: usize) {
+        let num_entries = self.replication_state.len();
+        let run_end = self.cumsum[index];
+        let run_start = run_end - self.replication_state[index];
+        // Move all values forward 2 positions until 2 after current
+        self.replication_state
+            .copy_within(index..(num_entries - 2), index + 2);
+        self.cumsum.copy_within(index..(num_entries - 2), index + 2);
+        // Insert the new values
+        self.replication_state[index] = position - run_start;
+        self.replication_state[index + 1] = 1;
+        self.replication_state[index + 2] = run_end - 1 - position;
+        self.cumsum[index] = position;
+        self.cumsum[index + 1] = position + 1;
+        self.cumsum[index + 2] = run_end;
+        self.unreplicated_total -= 1;
+    }
+    /// Move `amount` bases from the run at `from` into its neighbour `to`
+    pub(crate) fn transfer(&mut self, from: usize, to: usize, amount: usize) {
+        self.replication_state[from] -= amount;
+        self.replication_state[to] += amount;
+        if from % 2 == 1 {
+            self.unreplicated_total -= amount;
+        }
+        if to % 2 == 1 {
+            self.unreplicated_total += amount;
+        }
+        if to < from {
+            self.cumsum[to] += amount;
+        } else {
+            self.cumsum[from] -= amount;
+        }
+    }
+    /// Join the runs either side of the empty run at `index`
+    pub(crate) fn merge_across(&mut self, index: usize) {
+        self.replication_state[index - 1] += self.replication_state[index + 1];
+        self.cumsum[index - 1] = self.cumsum[index + 1];
+        self.remove_pair(index);
+    }
+    /// Drop the runs at `index` and `index + 1`, shifting the rest back and padding the end
+    pub(crate) fn remove_pair(&mut self, index: usize) {
+        let num_entries = self.replication_state.len();
+        self.replication_state.copy_within((index + 2).., index);
+        self.cumsum.copy_within((index + 2).., index);
+        self.replication_state[num_entries - 2] = 0;
+        self.replication_state[num_entries - 1] = 0;
+        self.cumsum[num_entries - 2] = self.genome_length;
+        self.cumsum[num_entries - 1] = self.genome_length;
+    }
+}
+
+/// Draws the genome as a fixed-width track, `#` where at least half of the bases under
+/// a character are replicated and `.` elsewhere
+impl fmt::Display for Genome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let track: String = (0..DISPLAY_WIDTH)
+            .map(|column| {
+                let start = column * self.genome_length / DISPLAY_WIDTH;
+                let end = (column + 1) * self.genome_length / DISPLAY_WIDTH;
+                if self.fraction_replicated_in(start, end) >= 0.5 {
+                    '#'
+                } else {
+                    '.'
+                }
+            })
+            .collect();
+        write!(f, "{track}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::assert_invariants;
+
+    #[test]
+    fn intervals_join_adjacent_replicated_runs() {
+        let genome = Genome {
+            genome_length: 100,
+            replication_state: vec![10, 0, 5, 20, 15, 50, 0],
+            cumsum: vec![10, 10, 15, 35, 50, 100, 100],
+            ..Genome::new(100, 2)
+        };
+        assert_eq!(genome.replicated_intervals(), vec![0..15, 35..50]);
+        assert_eq!(genome.fraction_replicated(), 0.3);
+        assert_eq!(genome.is_replicated(14), Ok(true));
+        assert_eq!(genome.is_replicated(15), Ok(false));
+        assert_eq!(genome.is_replicated(35), Ok(true));
+        assert_eq!(genome.is_replicated(99), Ok(false));
+    }
+
+    #[test]
+    fn forks_sit_at_occupied_boundaries() {
+        let genome = Genome {
+            genome_length: 100,
+            replication_state: vec![0, 10, 5, 20, 15, 50, 0],
+            cumsum: vec![0, 10, 15, 35, 50, 100, 100],
+            ..Genome::new(100, 2)
+        };
+        assert_eq!(genome.active_fork_count(), 4);
+        assert_eq!(Genome::new(100, 2).active_fork_count(), 0);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn diff_reports_disagreeing_ranges() {
+        let genome = Genome {
+            genome_length: 100,
+            replication_state: vec![10, 0, 5, 20, 15, 50, 0],
+            cumsum: vec![10, 10, 15, 35, 50, 100, 100],
+            ..Genome::new(100, 2)
+        };
+        assert_eq!(genome.diff(&genome.clone()), vec![]);
+        let extended = Genome {
+            genome_length: 100,
+            replication_state: vec![10, 0, 5, 19, 16, 50, 0],
+            cumsum: vec![10, 10, 15, 34, 50, 100, 100],
+            ..Genome::new(100, 2)
+        };
+        assert_eq!(genome.diff(&extended), vec![(34, 35)]);
+        assert_eq!(extended.diff(&genome), vec![(34, 35)]);
+    }
+
+    #[test]
+    fn batch_status_matches_single_queries() {
+        let genome = Genome {
+            genome_length: 100,
+            replication_state: vec![10, 0, 5, 20, 15, 50, 0],
+            cumsum: vec![10, 10, 15, 35, 50, 100, 100],
+            ..Genome::new(100, 2)
+        };
+        let positions: Vec<usize> = (0..200).map(|i| (i * 37 + 11) % 100).collect();
+        let expected: Vec<bool> = positions
+            .iter()
+            .map(|&position| genome.is_replicated(position).unwrap())
+            .collect();
+        assert_eq!(genome.replicated_status_batch(&positions), expected);
+    }
+
+    #[test]
+    fn binned_fraction_splits_intervals_across_bins() {
+        let genome = Genome {
+            genome_length: 100,
+            replication_state: vec![10, 0, 5, 20, 15, 50, 0],
+            cumsum: vec![10, 10, 15, 35, 50, 100, 100],
+            ..Genome::new(100, 2)
+        };
+        assert_eq!(
+            genome.binned_fraction_replicated(4).unwrap(),
+            vec![0.6, 0.6, 0.0, 0.0]
+        );
+        assert!(matches!(
+            genome.binned_fraction_replicated(0),
+            Err(ReplicationError::InvalidConfig(_))
+        ));
+        assert!(matches!(
+            genome.binned_fork_count(0),
+            Err(ReplicationError::InvalidConfig(_))
+        ));
+    }
+
+    #[test]
+    fn window_fraction_credits_partial_overlap() {
+        let genome = Genome {
+            genome_length: 100,
+            replication_state: vec![50, 50, 0],
+            cumsum: vec![50, 100, 100],
+            ..Genome::new(100, 0)
+        };
+        assert_eq!(genome.fraction_replicated_in(40, 60), 0.5);
+        assert_eq!(genome.fraction_replicated_in(30, 60), 20.0 / 30.0);
+        assert_eq!(genome.fraction_replicated_in(0, 50), 1.0);
+        assert_eq!(genome.fraction_replicated_in(90, 500), 0.0);
+        assert_eq!(genome.fraction_replicated_in(40, 1_000), 10.0 / 60.0);
+        assert_eq!(genome.fraction_replicated_in(60, 60), 0.0);
+        assert_eq!(genome.fraction_replicated_in(70, 20), 0.0);
+    }
+
+    #[test]
+    fn compact_removes_spurious_empty_runs() {
+        let mut genome = Genome {
+            genome_length: 100,
+            replication_state: vec![0, 10, 0, 5, 20, 0, 15, 50, 0],
+            cumsum: vec![0, 10, 10, 15, 35, 35, 50, 100, 100],
+            ..Genome::new(100, 3)
+        };
+        genome.compact();
+        assert_eq!(genome.replication_state(), &[0, 15, 35, 50, 0, 0, 0, 0, 0]);
+        assert_eq!(genome.cumsum, vec![0, 15, 50, 100, 100, 100, 100, 100, 100]);
+        assert_eq!(genome.unreplicated_intervals(), vec![0..15, 50..100]);
+    }
+
+    #[test]
+    fn finalize_pads_hand_built_state() {
+        let genome = Genome::new(100, 0)
+            .with_replication_state(vec![10, 30, 0, 20, 40])
+            .finalize(2)
+            .unwrap();
+        assert_eq!(genome.replication_state(), &[10, 50, 40, 0, 0, 0, 0, 0, 0]);
+        assert_invariants(&genome);
+        assert_eq!(genome.unreplicated_intervals(), vec![10..60]);
+
+        let uncovered = Genome::new(100, 0).with_replication_state(vec![10, 30]);
+        assert!(matches!(
+            uncovered.finalize(2),
+            Err(ReplicationError::InvalidConfig(_))
+        ));
+    }
+
+    #[test]
+    fn arm_fractions_split_at_centromere() {
+        let mut genome = Genome::new(100, 2).with_centromere(40, false);
+        genome.set_runs(vec![40, 60, 0, 0, 0, 0, 0]);
+        assert_eq!(genome.arm_fraction(), (1.0, 0.0));
+        assert_eq!(Genome::new(100, 2).arm_fraction(), (0.0, 0.0));
+    }
+
+    #[test]
+    fn display_draws_replicated_track() {
+        let genome = Genome {
+            genome_length: 1_000,
+            replication_state: vec![500, 500, 0],
+            cumsum: vec![500, 1_000, 1_000],
+            ..Genome::new(1_000, 0)
+        };
+        assert_eq!(
+            genome.to_string(),
+            format!("{}{}", "#".repeat(40), ".".repeat(40))
+        );
+    }
+
+    #[test]
+    fn density_profile_is_relative_to_densest_region() {
+        let genome = Genome::new(100, 2).with_density_profile(vec![(0..10, 4.0), (10..20, 0.0)]);
+        assert_eq!(genome.origin_density_at(5), 1.0);
+        assert_eq!(genome.origin_density_at(15), 0.0);
+        assert_eq!(genome.origin_density_at(50), 0.25);
+    }
+
+    #[test]
+    fn out_of_bounds_query_is_an_error() {
+        let genome = Genome::new(100, 2);
+        assert_eq!(
+            genome.is_replicated(100),
+            Err(ReplicationError::OutOfBounds {
+                position: 100,
+                genome_length: 100
+            })
+        );
+    }
+}
@@ -0,0 +1,50 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+#[cfg(feature = "std")]
+mod batch;
+mod cell;
+mod diploid;
+mod error;
+#[cfg(feature = "std")]
+mod export;
+mod genome;
+mod multi;
+#[cfg(feature = "std")]
+mod report;
+mod sampler;
+mod stats;
+mod steps;
+#[cfg(test)]
+mod test_utils;
+mod timing;
+
+#[cfg(feature = "std")]
+pub use batch::{
+    batch_replicate, batch_s_phase_durations, fork_count_timeseries, fraction_at_iteration,
+    origin_landscape, timing_matrix, timing_variance,
+};
+pub use cell::{
+    AdaptiveStep, Cell, CellConfig, CellSnapshot, CellState, CheckpointConfig, CohesionSites,
+    FiringEvent, FiringSchedule, ForkSlowdown, ForkSuppression, GPhaseModel, Lesion, LicensingMode,
+    ReplicationSummary, RngCheckpoint, SpeedJitter, BASES_PER_REPLICATOR,
+    DEFAULT_FIRING_PROBABILITY, DEFAULT_G_PHASE_PROB, DEFAULT_MINUTES_PER_ITERATION,
+    DEFAULT_OKAZAKI_FRAGMENT_SIZE, DEFAULT_REPLICATION_RATE, DEFAULT_SEED, HUMAN_CHR1_LENGTH,
+};
+pub use diploid::DiploidGenome;
+pub use error::ReplicationError;
+#[cfg(feature = "std")]
+pub use export::{CoordinateUnit, ExportOptions};
+pub use genome::{DomainTiming, Genome, ReplicationDomain};
+pub use multi::{chromosome_rng, MultiChromCell};
+#[cfg(feature = "std")]
+pub use report::{run_full_analysis, FullReport};
+pub use sampler::{FiringSampler, FiringSamplerClone, LengthWeightedSampler};
+#[cfg(feature = "std")]
+pub use stats::std;
+pub use stats::{histogram, mean, variance};
+pub use steps::{AdvanceStats, StepEvent, StepIter};
+pub use timing::Clock;
+#[cfg(feature = "std")]
+pub use timing::TimingTrack;
@@ -1,217 +1,226 @@
-use rand_chacha::ChaCha8Rng;
+use anyhow::Result;
+use clap::{Parser, ValueEnum};
+use replicon::{
+    Cell, CellConfig, CoordinateUnit, ExportOptions, ReplicationSummary, BASES_PER_REPLICATOR,
+    DEFAULT_FIRING_PROBABILITY, DEFAULT_G_PHASE_PROB, DEFAULT_REPLICATION_RATE, DEFAULT_SEED,
+    HUMAN_CHR1_LENGTH,
+};
+use std::fs::File;
+use std::io::{self, Write};
+use std::path::PathBuf;
 use std::time::Instant;
-use rand::prelude::*;
 
-#[derive(Debug, Default, Clone, PartialEq)]
-enum CellState {
-    #[default]
-    GPhase,
-    SPhase,
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+enum OutputFormat {
+    Csv,
+    Json,
 }
 
-#[derive(Debug, Clone)]
-struct Cell {
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+enum Units {
+    Bp,
+    Kb,
+    Mb,
+}
+
+impl From<Units> for CoordinateUnit {
+    fn from(units: Units) -> Self {
+        match units {
+            Units::Bp => CoordinateUnit::Bp,
+            Units::Kb => CoordinateUnit::Kb,
+            Units::Mb => CoordinateUnit::Mb,
+        }
+    }
+}
+
+/// Simulate replication timing of a single chromosome
+#[derive(Debug, Parser)]
+#[command(version, about)]
+struct Args {
+    /// Length of the simulated genome in bases
+    #[arg(long, default_value_t = HUMAN_CHR1_LENGTH)]
     genome_length: usize,
-    unassigned_replicators: usize,
-    cell_state: CellState,
+    /// Number of replication machineries, defaults to one per 1.6Mb (at least one)
+    #[arg(long)]
+    num_replicators: Option<usize>,
+    /// Bases replicated by each fork per iteration
+    #[arg(long, default_value_t = DEFAULT_REPLICATION_RATE)]
     replication_rate: usize,
-    replication_state: Vec<usize>,
+    /// Seed for the simulation RNG
+    #[arg(long, default_value_t = DEFAULT_SEED)]
+    seed: u64,
+    /// Probability of remaining in G-phase at each warmup iteration
+    #[arg(long, default_value_t = DEFAULT_G_PHASE_PROB)]
+    g_phase_prob: f64,
+    /// Probability that a sampled unreplicated position fires as an origin
+    #[arg(long, default_value_t = DEFAULT_FIRING_PROBABILITY)]
+    firing_prob: f64,
+    /// Format of the run summary
+    #[arg(long, value_enum, default_value_t = OutputFormat::Csv)]
+    output: OutputFormat,
+    /// File to write the run summary to, stdout if not given
+    #[arg(long)]
+    output_file: Option<PathBuf>,
+    /// Units of the genome length in the run summary
+    #[arg(long, value_enum, default_value_t = Units::Bp)]
+    units: Units,
+    /// Decimal places of the genome length in the run summary, shortest exact form if
+    /// not given
+    #[arg(long)]
+    precision: Option<usize>,
 }
 
-impl Cell {
-    fn new(genome_length: usize, num_replicators: usize, replication_rate: usize) -> Self {
-        let mut start_vec: Vec<usize> = vec![0; (num_replicators * 2) + 3];
-        start_vec[1] = genome_length;
-        Cell {
-            genome_length,
-            unassigned_replicators: num_replicators,
-            cell_state: CellState::GPhase,
-            replication_rate,
-            replication_state: start_vec,
-        }
+impl Args {
+    fn num_replicators(&self) -> usize {
+        self.num_replicators
+            .unwrap_or((self.genome_length / BASES_PER_REPLICATOR).max(1))
     }
-    fn is_replicated(&self, position: usize) -> bool {
-        // Handle out of bounds
-        if position >= self.genome_length {
-            panic!(
-                "Index {} is too large, cannot index beyond genome length {}",
-                position, self.genome_length
-            )
-        }
-        // Identify correct insertion location
-        let mut check_index: usize = 0;
-        let mut cumsum: usize = 0;
-        for (ind, value) in self.replication_state.iter().enumerate() {
-            check_index = ind;
-            cumsum += value;
-            if position < cumsum {
-                break;
-            }
+    fn config(&self) -> CellConfig {
+        CellConfig {
+            genome_length: self.genome_length,
+            num_replicators: self.num_replicators(),
+            replication_rate: self.replication_rate,
+            firing_probability: self.firing_prob,
+            g_phase_prob: self.g_phase_prob,
+            seed: self.seed,
         }
-        // All even indexes are replicated ranges
-        check_index % 2 == 0
     }
-    fn is_fully_replicated(&self) -> bool {
-        // genome is fully replicated if there's no positions in unreplicated (odd) storage indexes
-        for (ind, val) in self.replication_state.iter().enumerate() {
-            if (ind % 2 != 0) & (*val != 0) {
-                return false;
-            }
+    fn export_options(&self) -> ExportOptions {
+        ExportOptions {
+            units: self.units.into(),
+            precision: self.precision,
         }
-        true
     }
-    fn assign_replicators(&mut self, rng_obj: &mut ChaCha8Rng) {
-        // If there are unassigned replicators, assign them
-        while self.unassigned_replicators > 0 {
-            // Calculate number of unreplicated regions
-            let num_unreplicated: usize = self.replication_state
-                .iter()
-                .enumerate()
-                .filter_map(|(ind, val)| {
-                    if ind % 2 != 0 {
-                        Some(val)
-                    } else {
-                        None
-                    }
-                })
-                .sum();
-            if num_unreplicated == 0 {
-                return
-            }
-
-            // Sample from the number of unreplicated regions, storing genome position
-            let mut cumsum: usize = 0;
-            let mut insert_index: usize = 0;
-            let mut position: isize = -1; 
-            while position < 0 {
-                let sample_unreplicated_index: usize = rng_obj.gen_range(0..num_unreplicated);
-                // Convert index to genome position
-                cumsum = 0;
-                let mut genome_position: usize = 0;
-                let mut unreplicated_remainder: usize = sample_unreplicated_index;
-                for (ind, length) in self.replication_state.iter().enumerate() {
-                    if ind % 2 != 0 {
-                        if unreplicated_remainder < *length {
-                            insert_index = ind;
-                            genome_position = cumsum + unreplicated_remainder;
-                            cumsum += length;
-                            break
-                        }
-                        unreplicated_remainder -= length;
-                    }
-                    cumsum += length;                            
-                }
-                // Random chance check if this position can be used
-                if rng_obj.gen::<f64>() > 0.9 {
-                    position = genome_position as isize;
-                };
-            }
-            let position = position as usize;
-
-            // Get current bin state and work out adjacent values
-            let current_length = self.replication_state[insert_index];
-            let left_count = position + current_length - cumsum;
-            let right_count = (cumsum - 1) - position;
-            // Move all values forward 2 positions until 2 after current
-            for index in ((insert_index + 2)..self.replication_state.len()).rev() {
-                self.replication_state[index] = self.replication_state[index - 2];
-            }
-            // Insert the new values
-            self.replication_state[insert_index + 2] = right_count;
-            self.replication_state[insert_index + 1] = 1;
-            self.replication_state[insert_index] = left_count;
+}
 
-            // Update number of repliactors
-            self.unassigned_replicators -= 1;
+fn write_summary(
+    writer: &mut impl Write,
+    args: &Args,
+    summary: &ReplicationSummary,
+) -> io::Result<()> {
+    let genome_length = args.export_options().coordinate(args.genome_length);
+    match args.output {
+        OutputFormat::Csv => {
+            writeln!(
+                writer,
+                "genome_length,num_replicators,replication_rate,seed,g_phase_prob,firing_prob,warmup_iterations,num_iterations"
+            )?;
+            writeln!(
+                writer,
+                "{},{},{},{},{},{},{},{}",
+                genome_length,
+                args.num_replicators(),
+                args.replication_rate,
+                args.seed,
+                args.g_phase_prob,
+                args.firing_prob,
+                summary.warmup_iterations,
+                summary.num_iterations
+            )
         }
+        OutputFormat::Json => writeln!(
+            writer,
+            "{{\"genome_length\":{},\"num_replicators\":{},\"replication_rate\":{},\"seed\":{},\"g_phase_prob\":{},\"firing_prob\":{},\"warmup_iterations\":{},\"num_iterations\":{}}}",
+            genome_length,
+            args.num_replicators(),
+            args.replication_rate,
+            args.seed,
+            args.g_phase_prob,
+            args.firing_prob,
+            summary.warmup_iterations,
+            summary.num_iterations
+        ),
     }
-    fn replicate_and_merge(&mut self) {
-        let num_entries = self.replication_state.len();
+}
 
-        for index in (1..(num_entries - 1)).step_by(2).rev() {
-            // At each unreplicated region, give one of the values to
-            // adjacent occupied replication regions
-            let left_occupied = self.replication_state[index - 1] > 0;
-            let right_occupied = self.replication_state[index + 1] > 0;
+fn main() -> Result<()> {
+    let args = Args::parse();
 
-            if self.replication_state[index] > 0 {
-                if left_occupied {
-                    let move_amount = self.replication_state[index].min(self.replication_rate);
-                    self.replication_state[index - 1] += move_amount;
-                    self.replication_state[index] -= move_amount;
-                }
-                if (right_occupied) && (self.replication_state[index] > 0) {
-                    let move_amount = self.replication_state[index].min(self.replication_rate);
-                    self.replication_state[index + 1] += move_amount;
-                    self.replication_state[index] -= move_amount;
-                }
-            }
+    // Build the cell from the command line parameters
+    let config = args.config();
+    let mut cell = Cell::from_config(&config)?;
 
-            // Merge if now 0 and both neighbours are occupied
-            if (self.replication_state[index] == 0) && left_occupied && right_occupied {
-                // Update left by addding right, then shift all rest
-                self.replication_state[index - 1] += self.replication_state[index + 1];
-                for step_index in index..(num_entries - 2) {
-                    self.replication_state[step_index] = self.replication_state[step_index + 2];
-                }
-                self.replication_state[&num_entries - 2] = 0;
-                self.replication_state[&num_entries - 1] = 0;
+    // Run replication
+    let now = Instant::now();
+    let summary = cell.full_replication(config.g_phase_prob, config.seed)?;
+    eprintln!("Time taken: {:.2?}", now.elapsed());
 
-                // Count the merge
-                self.unassigned_replicators += 1;
-            }
-        }
-        // Edge case for merging genome start
-        if (self.replication_state[0] == 0) && (self.replication_state[1] == 0) {
-            for step_index in 0..(num_entries - 2) {
-                self.replication_state[step_index] = self.replication_state[step_index + 2];
-            }
-            self.replication_state[&num_entries - 2] = 0;
-            self.replication_state[&num_entries - 1] = 0;
-        }
+    match &args.output_file {
+        Some(path) => write_summary(&mut File::create(path)?, &args, &summary)?,
+        None => write_summary(&mut io::stdout().lock(), &args, &summary)?,
     }
-    fn full_replication(&mut self, g_phase_prob: f64) {
-        let mut rng = ChaCha8Rng::seed_from_u64(1701);
-
-        // Loop until enters G-phase
-        let mut num_warmup_iters: isize = 0;
-        while self.cell_state == CellState::GPhase {
-            if rng.gen::<f64>() > g_phase_prob {
-                self.cell_state = CellState::SPhase;
-            }
-            num_warmup_iters += 1;
-        }
-        println!("Entered S phase after {num_warmup_iters:?} warmups!");
+    Ok(())
+}
 
-        // Replication run
-        let now = Instant::now();
-        let mut num_iterations: usize = 0;
-        while !self.is_fully_replicated() {
-            self.assign_replicators(&mut rng);
-            self.replicate_and_merge();
-            num_iterations += 1;
-        }
-        println!("Time taken: {:.2?}", now.elapsed());
-        println!(
-            "Converged in {} iterations to: {:?}",
-            &num_iterations, &self.replication_state
-        );
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_cli_arguments() {
+        let args = Args::try_parse_from([
+            "replicon",
+            "--genome-length",
+            "10000",
+            "--num-replicators",
+            "4",
+            "--replication-rate",
+            "10",
+            "--seed",
+            "42",
+            "--g-phase-prob",
+            "0.5",
+            "--firing-prob",
+            "0.2",
+            "--output",
+            "json",
+        ])
+        .unwrap();
+        assert_eq!(args.genome_length, 10_000);
+        assert_eq!(args.num_replicators(), 4);
+        assert_eq!(args.replication_rate, 10);
+        assert_eq!(args.seed, 42);
+        assert_eq!(args.g_phase_prob, 0.5);
+        assert_eq!(args.firing_prob, 0.2);
+        assert_eq!(args.output, OutputFormat::Json);
+        assert_eq!(args.output_file, None);
     }
-}
 
-fn main() {
-    // Create a prototype genome
-    let chrom_size: usize = 500_000_000;
-    let num_replicators: usize = chrom_size / 1_600_000;
-    let mut cell = Cell::new(chrom_size, num_replicators, 50);
+    #[test]
+    fn defaults_num_replicators_from_genome_length() {
+        let args = Args::try_parse_from(["replicon", "--genome-length", "16000000"]).unwrap();
+        assert_eq!(args.num_replicators(), 10);
+        assert_eq!(args.output, OutputFormat::Csv);
+    }
 
-    // Basic checking
-    println!("{:}", cell.is_replicated(100_000));
-    println!("{:}", cell.is_fully_replicated());
+    #[test]
+    fn defaults_match_human_chr1_preset() {
+        let args = Args::try_parse_from(["replicon"]).unwrap();
+        assert_eq!(args.config(), CellConfig::preset_human_chr1());
+    }
 
-    // Run replication
-    cell.full_replication(0.9);
+    #[test]
+    fn summary_genome_length_follows_units_and_precision() {
+        let args = Args::try_parse_from([
+            "replicon",
+            "--genome-length",
+            "2500000",
+            "--units",
+            "mb",
+            "--precision",
+            "2",
+            "--output",
+            "json",
+        ])
+        .unwrap();
+        let summary = Cell::from_config(&args.config())
+            .unwrap()
+            .full_replication(args.g_phase_prob, args.seed)
+            .unwrap();
+        let mut written: Vec<u8> = Vec::new();
+        write_summary(&mut written, &args, &summary).unwrap();
+        assert!(String::from_utf8(written)
+            .unwrap()
+            .starts_with("{\"genome_length\":2.50,"));
+    }
 }
-
-#[cfg(test)]
-mod tests {}
@@ -0,0 +1,125 @@
+use crate::cell::{Cell, CellConfig, GPhaseModel, ReplicationSummary};
+use crate::error::ReplicationError;
+use alloc::vec;
+use alloc::vec::Vec;
+use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+
+/// RNG for chromosome `chrom_index` of a cell seeded with `master_seed`. Each chromosome
+/// draws from its own ChaCha stream, so its trajectory does not depend on which other
+/// chromosomes are simulated or in what order.
+pub fn chromosome_rng(master_seed: u64, chrom_index: u64) -> ChaCha8Rng {
+    let mut rng = ChaCha8Rng::seed_from_u64(master_seed);
+    // Stream 0 is left for the cell-wide G-phase warmup
+    rng.set_stream(chrom_index + 1);
+    rng
+}
+
+/// A cell holding several chromosomes that enter S-phase together but replicate with
+/// independent replicator pools and RNG streams
+#[derive(Debug, Clone)]
+pub struct MultiChromCell {
+    chromosomes: Vec<Cell>,
+    /// Iteration at which each chromosome finished, `None` until it has
+    completion_iterations: Vec<Option<usize>>,
+}
+
+impl MultiChromCell {
+    pub fn from_configs(configs: &[CellConfig]) -> Result<Self, ReplicationError> {
+        let chromosomes = configs
+            .iter()
+            .map(Cell::from_config)
+            .collect::<Result<Vec<Cell>, ReplicationError>>()?;
+        Ok(MultiChromCell {
+            completion_iterations: vec![None; chromosomes.len()],
+            chromosomes,
+        })
+    }
+    pub fn chromosomes(&self) -> &[Cell] {
+        &self.chromosomes
+    }
+    pub fn is_fully_replicated(&self) -> bool {
+        self.chromosomes.iter().all(Cell::is_fully_replicated)
+    }
+    /// Run the shared G-phase warmup from `seed`, then replicate each chromosome with
+    /// its own [`chromosome_rng`]
+    pub fn full_replication(
+        &mut self,
+        g_phase: impl Into<GPhaseModel>,
+        seed: u64,
+    ) -> Result<Vec<ReplicationSummary>, ReplicationError> {
+        let g_phase = g_phase.into();
+        g_phase.validate()?;
+        let warmup_iterations = g_phase.warmup(&mut ChaCha8Rng::seed_from_u64(seed));
+        self.chromosomes
+            .iter_mut()
+            .zip(&mut self.completion_iterations)
+            .enumerate()
+            .map(|(chrom_index, (chromosome, completion))| {
+                let mut rng = chromosome_rng(seed, chrom_index as u64);
+                let mut summary = chromosome.run_until_fraction(1.0, &mut rng)?;
+                summary.warmup_iterations = warmup_iterations;
+                *completion = Some(summary.s_phase.end);
+                Ok(summary)
+            })
+            .collect()
+    }
+    /// Finished chromosomes as `(chrom_index, iteration)` pairs in the order they
+    /// completed, ties broken by index
+    pub fn completion_order(&self) -> Vec<(usize, usize)> {
+        let mut order: Vec<(usize, usize)> = self
+            .completion_iterations
+            .iter()
+            .enumerate()
+            .filter_map(|(chrom_index, completion)| {
+                completion.map(|iteration| (chrom_index, iteration))
+            })
+            .collect();
+        order.sort_by_key(|&(chrom_index, iteration)| (iteration, chrom_index));
+        order
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn smaller_chromosome_finishes_first() {
+        let large = CellConfig::new(200_000, 4);
+        let small = CellConfig::new(10_000, 4);
+        let mut multi = MultiChromCell::from_configs(&[large, small]).unwrap();
+        assert!(multi.completion_order().is_empty());
+        multi.full_replication(0.5, 7).unwrap();
+        let order = multi.completion_order();
+        assert_eq!(order.len(), 2);
+        assert_eq!(order[0].0, 1);
+        assert!(order[0].1 < order[1].1);
+    }
+
+    #[test]
+    fn chromosome_trajectory_is_independent_of_neighbours() {
+        let first = CellConfig {
+            genome_length: 40_000,
+            num_replicators: 6,
+            ..CellConfig::default()
+        };
+        let second = CellConfig {
+            genome_length: 25_000,
+            num_replicators: 4,
+            ..CellConfig::default()
+        };
+        let mut multi = MultiChromCell::from_configs(&[first, second.clone()]).unwrap();
+        multi.full_replication(second.g_phase_prob, 99).unwrap();
+        assert!(multi.is_fully_replicated());
+
+        let mut solo = Cell::from_config(&second).unwrap();
+        solo.run_until_fraction(1.0, &mut chromosome_rng(99, 1))
+            .unwrap();
+        assert_eq!(solo.firing_log(), multi.chromosomes()[1].firing_log());
+        assert_ne!(
+            multi.chromosomes()[0].firing_log(),
+            multi.chromosomes()[1].firing_log()
+        );
+    }
+}
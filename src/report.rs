@@ -0,0 +1,66 @@
+use crate::cell::{Cell, CellConfig, ReplicationSummary};
+use crate::error::ReplicationError;
+use crate::timing::TimingTrack;
+
+/// Every standard readout of a single run, see [`run_full_analysis`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct FullReport {
+    pub summary: ReplicationSummary,
+    pub timing_track: TimingTrack,
+    /// Fired origins as (iteration, position) pairs, in firing order
+    pub firing_log: Vec<(usize, usize)>,
+    pub termination_sites: Vec<usize>,
+    pub inter_origin_distances: Vec<usize>,
+    /// Replicated fraction after each S-phase iteration
+    pub fraction_curve: Vec<f64>,
+    pub replicon_count: usize,
+    pub passive_origins: Vec<usize>,
+}
+
+/// Run one cell from `config` with every recorder enabled and collect the results
+pub fn run_full_analysis(config: &CellConfig, seed: u64) -> Result<FullReport, ReplicationError> {
+    let mut cell = Cell::from_config(config)?
+        .with_timing_track()
+        .with_fraction_curve();
+    let summary = cell.full_replication(config.g_phase_prob, seed)?;
+    Ok(FullReport {
+        summary,
+        timing_track: cell
+            .timing_track()
+            .cloned()
+            .unwrap_or_else(|| TimingTrack::new(0)),
+        firing_log: cell.firing_log().to_vec(),
+        termination_sites: cell.termination_sites().to_vec(),
+        inter_origin_distances: cell.inter_origin_distances(),
+        fraction_curve: cell.fraction_curve().unwrap_or_default().to_vec(),
+        replicon_count: cell.replicon_count(),
+        passive_origins: cell.passive_origins().to_vec(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn full_report_is_self_consistent() {
+        let config = CellConfig {
+            genome_length: 30_000,
+            num_replicators: 6,
+            ..CellConfig::default()
+        };
+        let report = run_full_analysis(&config, 4).unwrap();
+        assert_eq!(
+            report.firing_log.len(),
+            report.replicon_count + report.passive_origins.len()
+        );
+        assert_eq!(report.termination_sites.len(), report.replicon_count - 1);
+        assert_eq!(
+            report.inter_origin_distances.len(),
+            report.replicon_count - 1
+        );
+        assert_eq!(report.fraction_curve.len(), report.summary.num_iterations);
+        assert_eq!(report.fraction_curve.last(), Some(&1.0));
+        assert!(report.timing_track.times().iter().all(Option::is_some));
+    }
+}
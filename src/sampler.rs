@@ -0,0 +1,155 @@
+use crate::genome::Genome;
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::fmt::Debug;
+use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+
+/// Strategy for proposing where the next origin fires
+///
+/// Proposals are still subject to the cell's firing probability, so a sampler only
+/// chooses candidates. Returning `None` leaves the remaining replicators idle this step.
+pub trait FiringSampler: Debug + Send + FiringSamplerClone {
+    /// Called before sampling from a genome that may have changed since the last call,
+    /// so per-genome lookups can be built once rather than per sample
+    fn prepare(&mut self, _genome: &Genome) {}
+    fn sample(&mut self, genome: &Genome, rng: &mut ChaCha8Rng) -> Option<usize>;
+}
+
+/// Lets a [`Cell`](crate::Cell) holding a boxed sampler stay `Clone`
+pub trait FiringSamplerClone {
+    fn clone_box(&self) -> Box<dyn FiringSampler>;
+}
+
+impl<T: FiringSampler + Clone + 'static> FiringSamplerClone for T {
+    fn clone_box(&self) -> Box<dyn FiringSampler> {
+        Box::new(self.clone())
+    }
+}
+
+impl Clone for Box<dyn FiringSampler> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
+
+/// Samples uniformly over unreplicated bases, so longer unreplicated runs are chosen
+/// in proportion to their length. Runs shorter than the genome's
+/// [`min_replicon_size`](Genome::min_replicon_size) are never chosen.
+#[derive(Debug, Default, Clone)]
+pub struct LengthWeightedSampler {
+    /// Running total of unreplicated bases up to the end of each unreplicated run
+    unreplicated_ends: Vec<usize>,
+}
+
+impl FiringSampler for LengthWeightedSampler {
+    fn prepare(&mut self, genome: &Genome) {
+        let min_length = genome.min_replicon_size();
+        self.unreplicated_ends = genome
+            .replication_state
+            .iter()
+            .skip(1)
+            .step_by(2)
+            .scan(0, |total, &length| {
+                if length >= min_length {
+                    *total += length;
+                }
+                Some(*total)
+            })
+            .collect();
+    }
+    fn sample(&mut self, genome: &Genome, rng: &mut ChaCha8Rng) -> Option<usize> {
+        let num_unreplicated = self.unreplicated_ends.last().copied().unwrap_or(0);
+        if num_unreplicated == 0 {
+            return None;
+        }
+        // Find the unreplicated run holding the sampled unreplicated index
+        let sample_unreplicated_index = rng.gen_range(0..num_unreplicated);
+        let run = self
+            .unreplicated_ends
+            .partition_point(|&end| end <= sample_unreplicated_index);
+        // Count back from the run's end by the unreplicated bases sampled past
+        let bases_from_end = self.unreplicated_ends[run] - sample_unreplicated_index;
+        Some(genome.cumsum[2 * run + 1] - bases_from_end)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cell::Cell;
+
+    #[derive(Debug, Clone)]
+    struct StartSampler;
+
+    impl FiringSampler for StartSampler {
+        fn sample(&mut self, _genome: &Genome, _rng: &mut ChaCha8Rng) -> Option<usize> {
+            Some(0)
+        }
+    }
+
+    /// Linear scan over the runs, as `assign_replicators` originally mapped samples
+    fn linear_scan_sample(genome: &Genome, rng: &mut ChaCha8Rng) -> Option<usize> {
+        let num_unreplicated: usize = genome.replication_state.iter().skip(1).step_by(2).sum();
+        if num_unreplicated == 0 {
+            return None;
+        }
+        let mut unreplicated_remainder: usize = rng.gen_range(0..num_unreplicated);
+        for (ind, length) in genome.replication_state.iter().enumerate() {
+            if ind % 2 != 0 {
+                if unreplicated_remainder < *length {
+                    return Some(genome.cumsum[ind] - length + unreplicated_remainder);
+                }
+                unreplicated_remainder -= length;
+            }
+        }
+        None
+    }
+
+    #[test]
+    fn length_weighted_sampler_matches_linear_scan() {
+        let mut cell = Cell::new(30_000, 6, 50);
+        cell.run_until_fraction(0.4, &mut ChaCha8Rng::seed_from_u64(2))
+            .unwrap();
+        let genome = cell.genome();
+        let mut sampler = LengthWeightedSampler::default();
+        sampler.prepare(genome);
+        let mut rng = ChaCha8Rng::seed_from_u64(9);
+        let mut reference_rng = ChaCha8Rng::seed_from_u64(9);
+        for _ in 0..1_000 {
+            let position = sampler.sample(genome, &mut rng);
+            assert_eq!(position, linear_scan_sample(genome, &mut reference_rng));
+            assert_eq!(genome.is_replicated(position.unwrap()), Ok(false));
+        }
+    }
+
+    #[test]
+    fn equal_length_regions_map_to_their_own_coordinates() {
+        let mut genome = Genome::new(100, 1);
+        genome.set_runs(vec![0, 40, 20, 40, 0]);
+        let mut sampler = LengthWeightedSampler::default();
+        sampler.prepare(&genome);
+        let mut rng = ChaCha8Rng::seed_from_u64(5);
+        let positions: Vec<usize> = (0..2_000)
+            .map(|_| sampler.sample(&genome, &mut rng).unwrap())
+            .collect();
+        assert!(positions
+            .iter()
+            .all(|position| (0..40).contains(position) || (60..100).contains(position)));
+        // The first and last base of each region are reachable
+        for boundary in [0, 39, 60, 99] {
+            assert!(positions.contains(&boundary));
+        }
+    }
+
+    #[test]
+    fn custom_sampler_chooses_firing_sites() {
+        let mut cell = Cell::new(10_000, 1, 50)
+            .with_firing_probability(1.0)
+            .with_firing_sampler(StartSampler);
+        let mut rng = ChaCha8Rng::seed_from_u64(3);
+        cell.assign_replicators(&mut rng).unwrap();
+        assert_eq!(cell.firing_log(), &[(0, 0)]);
+        assert_eq!(cell.genome().is_replicated(0), Ok(true));
+    }
+}
@@ -0,0 +1,83 @@
+use crate::error::ReplicationError;
+use alloc::string::ToString;
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// Arithmetic mean, NaN for an empty slice
+pub fn mean(values: &[usize]) -> f64 {
+    values.iter().sum::<usize>() as f64 / values.len() as f64
+}
+
+/// Sample variance, NaN for fewer than two values
+pub fn variance(values: &[usize]) -> f64 {
+    let centre = mean(values);
+    let sum_squares: f64 = values
+        .iter()
+        .map(|&value| (value as f64 - centre) * (value as f64 - centre))
+        .sum();
+    sum_squares / (values.len() as f64 - 1.0)
+}
+
+/// Sample standard deviation, NaN for fewer than two values
+#[cfg(feature = "std")]
+pub fn std(values: &[usize]) -> f64 {
+    variance(values).sqrt()
+}
+
+/// Count values into bins of `bin_width` starting at zero, returning `(bin_start, count)`
+/// for every bin up to the one holding the largest value
+pub fn histogram(
+    values: &[usize],
+    bin_width: usize,
+) -> Result<Vec<(usize, usize)>, ReplicationError> {
+    if bin_width == 0 {
+        return Err(ReplicationError::InvalidConfig(
+            "bin_width must be positive".to_string(),
+        ));
+    }
+    let Some(max_value) = values.iter().max() else {
+        return Ok(Vec::new());
+    };
+    let mut counts = vec![0; max_value / bin_width + 1];
+    for value in values {
+        counts[value / bin_width] += 1;
+    }
+    Ok(counts
+        .into_iter()
+        .enumerate()
+        .map(|(bin, count)| (bin * bin_width, count))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn mean_and_std_of_known_values() {
+        let values = [2, 4, 4, 4, 5, 5, 7, 9];
+        assert_eq!(mean(&values), 5.0);
+        assert!((variance(&values) - 32.0 / 7.0).abs() < 1e-12);
+        assert!((std(&values) - (32.0_f64 / 7.0).sqrt()).abs() < 1e-12);
+        assert!(std(&[3]).is_nan());
+    }
+
+    #[test]
+    fn histogram_of_known_distances() {
+        let distances = [1, 2, 7, 12, 14, 14];
+        assert_eq!(
+            histogram(&distances, 5).unwrap(),
+            vec![(0, 2), (5, 1), (10, 3)]
+        );
+        assert_eq!(
+            histogram(&[20], 10).unwrap(),
+            vec![(0, 0), (10, 0), (20, 1)]
+        );
+        assert!(histogram(&[], 5).unwrap().is_empty());
+        assert!(matches!(
+            histogram(&distances, 0),
+            Err(ReplicationError::InvalidConfig(_))
+        ));
+    }
+}
@@ -0,0 +1,104 @@
+use crate::cell::Cell;
+use alloc::vec::Vec;
+use rand_chacha::ChaCha8Rng;
+
+/// What happened during one assign + replicate cycle
+#[derive(Debug, Clone, PartialEq)]
+pub struct StepEvent {
+    pub iteration: usize,
+    /// Positions of origins fired this step
+    pub fired: Vec<usize>,
+    /// Termination sites of forks merged this step
+    pub merged: Vec<usize>,
+    /// Replicated fraction of the genome after the step
+    pub fraction: f64,
+}
+
+/// Totals over the steps run by [`Cell::advance_by`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct AdvanceStats {
+    /// Steps actually run, fewer than requested if the genome finished replicating
+    pub steps: usize,
+    pub origins_fired: usize,
+    pub merges: usize,
+}
+
+/// Drives a cell one step per `next()`, ending once the genome is fully replicated or
+/// the cell's iteration limit is reached
+pub struct StepIter<'a> {
+    cell: &'a mut Cell,
+    rng: &'a mut ChaCha8Rng,
+}
+
+impl<'a> StepIter<'a> {
+    pub(crate) fn new(cell: &'a mut Cell, rng: &'a mut ChaCha8Rng) -> Self {
+        StepIter { cell, rng }
+    }
+}
+
+impl Iterator for StepIter<'_> {
+    type Item = StepEvent;
+
+    fn next(&mut self) -> Option<StepEvent> {
+        if self.cell.is_fully_replicated() || self.cell.check_converged().is_err() {
+            return None;
+        }
+        let iteration = self.cell.iteration();
+        let num_fired = self.cell.firing_log().len();
+        let num_merged = self.cell.termination_sites().len();
+        self.cell.step(self.rng).ok()?;
+        Some(StepEvent {
+            iteration,
+            fired: self.cell.firing_log()[num_fired..]
+                .iter()
+                .map(|(_, position)| *position)
+                .collect(),
+            merged: self.cell.termination_sites()[num_merged..].to_vec(),
+            fraction: self.cell.genome().fraction_replicated(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+
+    #[test]
+    fn steps_run_until_fully_replicated() {
+        let mut cell = Cell::new(30_000, 6, 50);
+        let mut rng = ChaCha8Rng::seed_from_u64(8);
+        let events: Vec<StepEvent> = cell.steps(&mut rng).collect();
+        assert!(cell.is_fully_replicated());
+        assert_eq!(events.last().unwrap().fraction, 1.0);
+        assert_eq!(events.last().unwrap().iteration, events.len() - 1);
+        let total_fired: usize = events.iter().map(|event| event.fired.len()).sum();
+        let total_merged: usize = events.iter().map(|event| event.merged.len()).sum();
+        assert_eq!(total_fired, cell.firing_log().len());
+        assert_eq!(total_merged, cell.termination_sites().len());
+        assert!(events
+            .windows(2)
+            .all(|pair| pair[0].fraction <= pair[1].fraction));
+    }
+
+    #[test]
+    fn advance_by_matches_single_steps() {
+        let mut stepped = Cell::new(30_000, 6, 50);
+        let mut rng = ChaCha8Rng::seed_from_u64(8);
+        let events: Vec<StepEvent> = stepped.steps(&mut rng).take(10).collect();
+
+        let mut advanced = Cell::new(30_000, 6, 50);
+        let mut rng = ChaCha8Rng::seed_from_u64(8);
+        let stats = advanced.advance_by(10, &mut rng).unwrap();
+        assert_eq!(advanced.genome(), stepped.genome());
+        assert_eq!(stats.steps, 10);
+        assert_eq!(
+            stats.origins_fired,
+            events.iter().map(|event| event.fired.len()).sum::<usize>()
+        );
+        assert_eq!(
+            stats.merges,
+            events.iter().map(|event| event.merged.len()).sum::<usize>()
+        );
+    }
+}
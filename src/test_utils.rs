@@ -0,0 +1,141 @@
+use crate::genome::Genome;
+
+/// Check the run-length encoding is well formed: runs tile the genome, the cached ends
+/// match the runs and there is room for the trailing replicated run
+pub(crate) fn assert_invariants(genome: &Genome) {
+    let state = &genome.replication_state;
+    assert_eq!(state.len(), genome.cumsum.len(), "cumsum length mismatch");
+    assert!(
+        state.len() >= 3 && state.len() % 2 == 1,
+        "bad state length {}",
+        state.len()
+    );
+    assert_eq!(
+        state.iter().sum::<usize>(),
+        genome.genome_length,
+        "runs do not tile the genome: {state:?}"
+    );
+    let mut total = 0;
+    for (index, length) in state.iter().enumerate() {
+        total += length;
+        assert_eq!(genome.cumsum[index], total, "stale cumsum at run {index}");
+    }
+    assert_eq!(
+        genome.unreplicated_total,
+        genome.scan_unreplicated_total(),
+        "stale unreplicated total"
+    );
+}
+
+pub(crate) fn assert_fully_replicated(genome: &Genome) {
+    assert_invariants(genome);
+    assert!(
+        genome.is_fully_replicated(),
+        "unreplicated runs remain: {:?}",
+        genome.unreplicated_intervals()
+    );
+}
+
+/// Every replicated coordinate, in order
+pub(crate) fn replicated_positions(genome: &Genome) -> Vec<usize> {
+    genome
+        .replicated_intervals()
+        .into_iter()
+        .flatten()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cell::{Cell, CellConfig, DEFAULT_G_PHASE_PROB};
+    use crate::diploid::DiploidGenome;
+    use crate::multi::MultiChromCell;
+    use rand::prelude::*;
+    use rand_chacha::ChaCha8Rng;
+
+    fn small_config() -> CellConfig {
+        CellConfig {
+            genome_length: 20_000,
+            num_replicators: 4,
+            ..CellConfig::default()
+        }
+    }
+
+    #[test]
+    fn genome_helpers_agree_with_queries() {
+        let mut genome = Genome::new(100, 1);
+        assert_invariants(&genome);
+        assert!(replicated_positions(&genome).is_empty());
+        genome.insert_origin(1, 40);
+        assert_invariants(&genome);
+        assert_eq!(replicated_positions(&genome), vec![40]);
+    }
+
+    #[test]
+    fn cell_replicates_fully() {
+        let mut cell = Cell::from_config(&small_config()).unwrap();
+        cell.full_replication(DEFAULT_G_PHASE_PROB, 3).unwrap();
+        assert_fully_replicated(cell.genome());
+        assert_eq!(replicated_positions(cell.genome()).len(), 20_000);
+    }
+
+    #[test]
+    fn diploid_homologs_replicate_fully() {
+        let mut diploid = DiploidGenome::from_config(&small_config()).unwrap();
+        diploid
+            .full_replication(DEFAULT_G_PHASE_PROB, [1, 2])
+            .unwrap();
+        for homolog in diploid.homologs() {
+            assert_fully_replicated(homolog.genome());
+        }
+    }
+
+    #[test]
+    fn multi_chromosomes_replicate_fully() {
+        let configs = [
+            small_config(),
+            CellConfig {
+                genome_length: 8_000,
+                ..small_config()
+            },
+        ];
+        let mut multi = MultiChromCell::from_configs(&configs).unwrap();
+        multi.full_replication(DEFAULT_G_PHASE_PROB, 5).unwrap();
+        for chromosome in multi.chromosomes() {
+            assert_fully_replicated(chromosome.genome());
+        }
+    }
+
+    /// Randomised cells checked after every step: runs tile the genome, no run has
+    /// wrapped around from an underflow, and the run finishes within its bound
+    #[test]
+    fn rle_invariants_hold_for_random_cells() {
+        let mut params = ChaCha8Rng::seed_from_u64(631);
+        for seed in 0..200 {
+            let genome_length = params.gen_range(1..20_000);
+            let num_replicators = params.gen_range(1..=genome_length.min(12));
+            let replication_rate = params.gen_range(5..200);
+            let firing_probability = params.gen_range(0.05..=1.0);
+            let max_iterations = 2 * genome_length;
+            let mut cell = Cell::new(genome_length, num_replicators, replication_rate)
+                .with_firing_probability(firing_probability)
+                .with_max_iterations(max_iterations);
+            let mut rng = ChaCha8Rng::seed_from_u64(seed);
+            while !cell.is_fully_replicated() {
+                cell.check_converged()
+                    .unwrap_or_else(|error| panic!("seed {seed}, {genome_length} bp: {error}"));
+                cell.step(&mut rng).unwrap();
+                assert_invariants(cell.genome());
+                assert!(
+                    cell.genome()
+                        .replication_state()
+                        .iter()
+                        .all(|&length| length <= genome_length),
+                    "seed {seed}: run length underflowed"
+                );
+            }
+            assert_fully_replicated(cell.genome());
+        }
+    }
+}
@@ -0,0 +1,338 @@
+#[cfg(feature = "std")]
+use crate::error::ReplicationError;
+#[cfg(feature = "std")]
+use crate::genome::Genome;
+#[cfg(feature = "std")]
+use range_set_blaze::RangeSetBlaze;
+#[cfg(feature = "std")]
+use std::iter;
+
+/// Conversion from iterations to real time units
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Clock {
+    /// Duration of one iteration in minutes
+    pub minutes_per_iteration: f64,
+    /// Bases each unhindered fork replicates per iteration
+    pub bp_per_iteration: usize,
+}
+
+impl Clock {
+    pub fn to_minutes(&self, iterations: usize) -> f64 {
+        iterations as f64 * self.minutes_per_iteration
+    }
+    /// Unhindered fork speed in kb/min
+    pub fn fork_speed_kb_per_min(&self) -> f64 {
+        self.bp_per_iteration as f64 / 1_000.0 / self.minutes_per_iteration
+    }
+}
+
+/// Per-base record of the iteration in which each base was replicated
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimingTrack {
+    times: Vec<Option<usize>>,
+    recorded: RangeSetBlaze<usize>,
+    /// Added to exported coordinates, see [`Genome::with_coordinate_offset`]
+    coordinate_offset: usize,
+}
+
+#[cfg(feature = "std")]
+impl TimingTrack {
+    pub fn new(genome_length: usize) -> Self {
+        TimingTrack {
+            times: vec![None; genome_length],
+            recorded: RangeSetBlaze::new(),
+            coordinate_offset: 0,
+        }
+    }
+    pub fn with_coordinate_offset(mut self, coordinate_offset: usize) -> Self {
+        self.coordinate_offset = coordinate_offset;
+        self
+    }
+    pub fn coordinate_offset(&self) -> usize {
+        self.coordinate_offset
+    }
+    /// Replication iteration of each base, `None` for bases not yet replicated
+    pub fn times(&self) -> &[Option<usize>] {
+        &self.times
+    }
+    /// Stamp bases replicated since the last call with `iteration`
+    pub(crate) fn record(&mut self, genome: &Genome, iteration: usize) {
+        let replicated: RangeSetBlaze<usize> = genome
+            .replicated_intervals()
+            .into_iter()
+            .map(|interval| interval.start..=interval.end - 1)
+            .collect();
+        for range in (&replicated - &self.recorded).ranges() {
+            for time in &mut self.times[range] {
+                *time = Some(iteration);
+            }
+        }
+        self.recorded = replicated;
+    }
+    /// Mean replication iteration of the replicated bases in `start..end`
+    pub fn mean_time(&self, start: usize, end: usize) -> f64 {
+        let times: Vec<usize> = self.times[start..end].iter().flatten().copied().collect();
+        times.iter().sum::<usize>() as f64 / times.len() as f64
+    }
+    /// Replication time of each base in minutes, `None` for bases not yet replicated
+    pub fn times_in_minutes(&self, clock: &Clock) -> Vec<Option<f64>> {
+        self.times
+            .iter()
+            .map(|time| time.map(|iteration| clock.to_minutes(iteration)))
+            .collect()
+    }
+    /// Iteration by which a `pct` fraction of the genome had replicated, with `pct` clamped
+    /// to [0, 1]. Unreplicated bases count as never finishing, so percentiles beyond the
+    /// replicated fraction return `usize::MAX`.
+    pub fn completion_time_percentile(&self, pct: f64) -> usize {
+        let mut times: Vec<usize> = self
+            .times
+            .iter()
+            .map(|time| time.unwrap_or(usize::MAX))
+            .collect();
+        if times.is_empty() {
+            return 0;
+        }
+        times.sort_unstable();
+        let rank = (pct.clamp(0.0, 1.0) * times.len() as f64).ceil() as usize;
+        times[rank.saturating_sub(1)]
+    }
+    /// Position replicated in the latest iteration, the lowest such position on ties and
+    /// 0 if nothing has replicated
+    pub fn last_replicated_position(&self) -> usize {
+        self.times
+            .iter()
+            .enumerate()
+            .filter_map(|(position, time)| time.map(|time| (time, position)))
+            .max_by(|(time, position), (other_time, other_position)| {
+                time.cmp(other_time).then(other_position.cmp(position))
+            })
+            .map_or(0, |(_, position)| position)
+    }
+    /// Mean replication iteration in each of `n_bins` equal bins, NaN for bins with no
+    /// replicated bases
+    pub fn binned_replication_time(&self, n_bins: usize) -> Result<Vec<f64>, ReplicationError> {
+        if n_bins == 0 {
+            return Err(ReplicationError::InvalidConfig(
+                "n_bins must be positive".to_string(),
+            ));
+        }
+        let genome_length = self.times.len();
+        Ok((0..n_bins)
+            .map(|bin| {
+                let start = bin * genome_length / n_bins;
+                let end = (bin + 1) * genome_length / n_bins;
+                self.mean_time(start, end)
+            })
+            .collect())
+    }
+    /// Space-time raster of replication, one column per each of `n_pos_bins` equal
+    /// position bins. Row 0 is the all-unreplicated genome, and row `k` for `k >= 1` is
+    /// the state after iteration `first + k - 1`, where `first` and `last` are the first
+    /// and last iterations in which any base replicated. That gives `last - first + 2`
+    /// rows, so a completed run of `num_iterations` whose first origin fired in iteration
+    /// `f` of S-phase has `num_iterations - f + 1`; iterations before any firing are not
+    /// drawn. An entry is 1 once every base of the bin has replicated and 0 before, ready
+    /// to render as an image. A track with nothing replicated gives no rows.
+    pub fn space_time_matrix(&self, n_pos_bins: usize) -> Result<Vec<Vec<u8>>, ReplicationError> {
+        if n_pos_bins == 0 {
+            return Err(ReplicationError::InvalidConfig(
+                "n_pos_bins must be positive".to_string(),
+            ));
+        }
+        let (Some(first), Some(last)) = (
+            self.times.iter().flatten().min(),
+            self.times.iter().flatten().max(),
+        ) else {
+            return Ok(Vec::new());
+        };
+        let genome_length = self.times.len();
+        // Iteration each bin finished in, `None` while any of its bases is unreplicated
+        let completed: Vec<Option<usize>> = (0..n_pos_bins)
+            .map(|bin| {
+                let start = bin * genome_length / n_pos_bins;
+                let end = (bin + 1) * genome_length / n_pos_bins;
+                self.times[start..end]
+                    .iter()
+                    .try_fold(0, |latest, time| time.map(|time| time.max(latest)))
+            })
+            .collect();
+        let unreplicated = vec![0; n_pos_bins];
+        Ok(iter::once(unreplicated)
+            .chain((*first..=*last).map(|iteration| {
+                completed
+                    .iter()
+                    .map(|done| u8::from(done.is_some_and(|done| done <= iteration)))
+                    .collect()
+            }))
+            .collect())
+    }
+    /// Local replication wave speed at each position in bp per iteration: the inverse of
+    /// the least-squares slope of replication time against position over a `window`
+    /// centred on it, shrunk at the genome ends. Unreplicated bases are left out, giving
+    /// NaN where fewer than two bases in the window have replicated. A flat window, such
+    /// as at an origin, gives infinity.
+    pub fn replication_wave_speed(&self, window: usize) -> Vec<f64> {
+        let genome_length = self.times.len();
+        let half = window / 2;
+        // Exact sums over the window's replicated bases of 1, dx, t, dx * t and dx^2,
+        // with dx the offset from the current position
+        let mut sums = [0i128; 5];
+        let update = |sums: &mut [i128; 5], position: usize, dx: i128, sign: i128| {
+            if let Some(time) = self.times[position] {
+                let t = time as i128;
+                for (total, term) in sums.iter_mut().zip([1, dx, t, dx * t, dx * dx]) {
+                    *total += sign * term;
+                }
+            }
+        };
+        for position in 0..=half.min(genome_length.saturating_sub(1)) {
+            update(&mut sums, position, position as i128, 1);
+        }
+        let mut speeds = Vec::with_capacity(genome_length);
+        for position in 0..genome_length {
+            let [n, x, t, xt, xx] = sums;
+            speeds.push(if n < 2 {
+                f64::NAN
+            } else {
+                let variance = n * xx - x * x;
+                let covariance = n * xt - x * t;
+                (variance as f64 / covariance as f64).abs()
+            });
+            // Recentre on the next position, then slide the window along by one
+            let [n, x, t, ..] = sums;
+            sums[1] -= n;
+            sums[3] -= t;
+            sums[4] += n - 2 * x;
+            if position >= half {
+                update(&mut sums, position - half, -(half as i128) - 1, -1);
+            }
+            if position + half + 1 < genome_length {
+                update(&mut sums, position + half + 1, half as i128, 1);
+            }
+        }
+        speeds
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    /// Track of a 100 base genome replicated by forks leaving position 50 at 10 bp/step
+    fn central_origin_track() -> TimingTrack {
+        let mut track = TimingTrack::new(100);
+        for iteration in 1..=5 {
+            let flank = 50 - 10 * iteration;
+            let mut genome = Genome::new(100, 1);
+            genome.set_runs(vec![0, flank, 100 - 2 * flank, flank, 0]);
+            track.record(&genome, iteration);
+        }
+        track
+    }
+
+    #[test]
+    fn central_origin_gives_v_shaped_timing() {
+        let track = central_origin_track();
+        assert_eq!(
+            track.binned_replication_time(10).unwrap(),
+            vec![5.0, 4.0, 3.0, 2.0, 1.0, 1.0, 2.0, 3.0, 4.0, 5.0]
+        );
+        assert!(TimingTrack::new(100).binned_replication_time(4).unwrap()[0].is_nan());
+        assert!(matches!(
+            track.binned_replication_time(0),
+            Err(ReplicationError::InvalidConfig(_))
+        ));
+    }
+
+    #[test]
+    fn space_time_matrix_fills_from_the_origin_outwards() {
+        let track = central_origin_track();
+        // Bases first replicate in iteration 1 and last in 5, giving 5 - 1 + 2 rows
+        let halves = track.space_time_matrix(2).unwrap();
+        assert_eq!(halves.len(), 6);
+        assert_eq!(halves[4], vec![0, 0]);
+        assert_eq!(halves.last(), Some(&vec![1, 1]));
+        let raster = track.space_time_matrix(10).unwrap();
+        assert_eq!(raster[0], vec![0; 10]);
+        assert_eq!(raster[1], vec![0, 0, 0, 0, 1, 1, 0, 0, 0, 0]);
+        assert_eq!(raster[3], vec![0, 0, 1, 1, 1, 1, 1, 1, 0, 0]);
+        // With one base per bin the origin's bins fill in the first iteration, so only
+        // the leading row shows the genome before S-phase
+        let fine = track.space_time_matrix(100).unwrap();
+        assert_eq!(fine[0], vec![0; 100]);
+        assert_eq!(
+            fine[1],
+            (0..100)
+                .map(|position| u8::from((40..60).contains(&position)))
+                .collect::<Vec<_>>()
+        );
+        assert!(TimingTrack::new(100)
+            .space_time_matrix(10)
+            .unwrap()
+            .is_empty());
+        assert!(matches!(
+            track.space_time_matrix(0),
+            Err(ReplicationError::InvalidConfig(_))
+        ));
+    }
+
+    #[test]
+    fn space_time_matrix_starts_at_the_first_firing() {
+        let mut cell = crate::cell::Cell::new(20_000, 1, 50)
+            .with_firing_probability(1e-5)
+            .with_timing_track();
+        let summary = cell.full_replication(0.0, 2).unwrap();
+        let (first_firing, _) = cell.firing_log()[0];
+        assert!(first_firing > 0);
+        let matrix = cell.timing_track().unwrap().space_time_matrix(20).unwrap();
+        assert_eq!(matrix.len(), summary.num_iterations - first_firing + 1);
+        assert_eq!(matrix[0], vec![0; 20]);
+        assert_eq!(matrix.last(), Some(&vec![1; 20]));
+    }
+
+    #[test]
+    fn last_replicated_position_is_at_an_end() {
+        let position = central_origin_track().last_replicated_position();
+        assert!(!(10..90).contains(&position));
+        assert_eq!(TimingTrack::new(100).last_replicated_position(), 0);
+    }
+
+    #[test]
+    fn clock_converts_iterations_to_minutes() {
+        let clock = Clock {
+            minutes_per_iteration: 2.0 / 60.0,
+            bp_per_iteration: 50,
+        };
+        assert!((clock.to_minutes(240 * 30) - 240.0).abs() < 1e-9);
+        assert!((clock.fork_speed_kb_per_min() - 1.5).abs() < 1e-9);
+        let track = central_origin_track();
+        assert_eq!(track.times_in_minutes(&clock)[0], Some(clock.to_minutes(5)));
+    }
+
+    #[test]
+    fn linear_gradient_has_constant_wave_speed() {
+        let track = TimingTrack {
+            times: (0..1_000).map(|position| Some(position / 10)).collect(),
+            ..TimingTrack::new(1_000)
+        };
+        let speeds = track.replication_wave_speed(101);
+        assert_eq!(speeds.len(), 1_000);
+        assert!(speeds.iter().all(|speed| (speed - 10.0).abs() < 0.5));
+        assert!(central_origin_track().replication_wave_speed(19)[50].is_infinite());
+    }
+
+    #[test]
+    fn completion_percentile_reads_sorted_times() {
+        let track = TimingTrack {
+            times: vec![Some(4), Some(1), Some(2), Some(3), None],
+            ..TimingTrack::new(5)
+        };
+        assert_eq!(track.completion_time_percentile(0.0), 1);
+        assert_eq!(track.completion_time_percentile(0.2), 1);
+        assert_eq!(track.completion_time_percentile(0.5), 3);
+        assert_eq!(track.completion_time_percentile(0.8), 4);
+        assert_eq!(track.completion_time_percentile(2.0), usize::MAX);
+    }
+}